@@ -3,11 +3,12 @@ pub mod card {
     use std::mem;
 
     use bevy::{prelude::*, reflect::GetTypeRegistration};
+    use serde::Deserialize;
 
     // #[derive(Component, Clone)]
     // struct Damage(usize);
 
-    #[derive(Component, Clone)]
+    #[derive(Component, Clone, Debug, Deserialize)]
     pub enum CardEffect {
         DirectDamage(i32),
         DamageOverTime {
@@ -19,6 +20,8 @@ pub mod card {
             duration: f32,
         },
         Heal(i32),
+        /// Adds to the player's shield, which soaks up damage for the rest of the fight.
+        Shield(i32),
     }
 
     pub enum StatusEffect {
@@ -32,6 +35,7 @@ pub mod card {
         Disarm,
     }
 
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     pub enum Element {
         Fire,
         Water,
@@ -40,6 +44,18 @@ pub mod card {
         Light,
     }
 
+    impl Element {
+        pub fn name(&self) -> &'static str {
+            match self {
+                Element::Fire => "Fire",
+                Element::Water => "Water",
+                Element::Earth => "Earth",
+                Element::Air => "Air",
+                Element::Light => "Light",
+            }
+        }
+    }
+
     #[derive(Component, Clone, Reflect)]
     pub enum ActiveEffect {
         DirectDamage(i32),
@@ -52,6 +68,7 @@ pub mod card {
             duration: Timer,
         },
         Heal(i32),
+        Shield(i32),
     }
 
     // pub struct Effect {
@@ -64,6 +81,70 @@ pub mod card {
         pub effects: Vec<ActiveEffect>,
     }
 
+    /// The cards the player owns for this run.
+    #[derive(Resource, Default)]
+    pub struct Deck {
+        pub cards: Vec<CardEffect>,
+    }
+
+    impl ActiveEffect {
+        /// Seconds left on effects that linger; `None` for ones that resolve at once.
+        pub fn remaining_secs(&self) -> Option<f32> {
+            match self {
+                ActiveEffect::DamageOverTime { duration, .. } | ActiveEffect::Stun { duration } => {
+                    Some(duration.remaining_secs())
+                }
+                ActiveEffect::DirectDamage(_) | ActiveEffect::Heal(_) | ActiveEffect::Shield(_) => {
+                    None
+                }
+            }
+        }
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                ActiveEffect::DirectDamage(_) => "Direct Damage",
+                ActiveEffect::DamageOverTime { .. } => "Damage Over Time",
+                ActiveEffect::Stun { .. } => "Stun",
+                ActiveEffect::Heal(_) => "Heal",
+                ActiveEffect::Shield(_) => "Shield",
+            }
+        }
+
+        pub fn describe(&self) -> String {
+            match self {
+                ActiveEffect::DirectDamage(damage) => format!("Takes {} damage", damage),
+                ActiveEffect::DamageOverTime {
+                    damage, frequency, ..
+                } => format!(
+                    "Takes {} damage every {:.1}s",
+                    damage,
+                    frequency.duration().as_secs_f32()
+                ),
+                ActiveEffect::Stun { .. } => "Can't play cards".to_string(),
+                ActiveEffect::Heal(heal) => format!("Heals {}", heal),
+                ActiveEffect::Shield(shield) => format!("Gains {} shield", shield),
+            }
+        }
+
+        pub fn icon_path(&self) -> &'static str {
+            match self {
+                ActiveEffect::DirectDamage(_) => "direct.png",
+                ActiveEffect::DamageOverTime { .. } => "dot.png",
+                ActiveEffect::Stun { .. } => "stun.png",
+                // No shield art yet, so it borrows the heal icon
+                ActiveEffect::Heal(_) | ActiveEffect::Shield(_) => "heal.png",
+            }
+        }
+    }
+
+    impl Effects {
+        pub fn is_stunned(&self) -> bool {
+            self.effects.iter().any(
+                |effect| matches!(effect, ActiveEffect::Stun { duration } if !duration.finished()),
+            )
+        }
+    }
+
     impl CardEffect {
         pub fn get_random_effect() -> CardEffect {
             use rand::random_range;
@@ -83,12 +164,139 @@ pub mod card {
             }
         }
 
+        /// Whether the effect lands on the one who played it rather than their opponent.
+        pub fn targets_self(&self) -> bool {
+            matches!(self, CardEffect::Heal(_) | CardEffect::Shield(_))
+        }
+
+        /// Whether the effect lingers on the target (DoTs, stuns) rather than resolving at once.
+        pub fn is_status(&self) -> bool {
+            matches!(
+                self,
+                CardEffect::DamageOverTime { .. } | CardEffect::Stun { .. }
+            )
+        }
+
+        /// The same effect landing as a critical hit.
+        pub fn critical(&self, multiplier: f32) -> CardEffect {
+            let scale = |value: i32| (value as f32 * multiplier).round() as i32;
+            match self {
+                CardEffect::DirectDamage(damage) => CardEffect::DirectDamage(scale(*damage)),
+                CardEffect::DamageOverTime {
+                    damage,
+                    duration,
+                    frequency,
+                } => CardEffect::DamageOverTime {
+                    damage: scale(*damage),
+                    duration: *duration,
+                    frequency: *frequency,
+                },
+                CardEffect::Stun { duration } => CardEffect::Stun {
+                    duration: duration * multiplier,
+                },
+                CardEffect::Heal(heal) => CardEffect::Heal(*heal),
+                CardEffect::Shield(shield) => CardEffect::Shield(*shield),
+            }
+        }
+
+        /// Plain attacks have no element.
+        pub fn element(&self) -> Option<Element> {
+            match self {
+                CardEffect::DirectDamage(_) => None,
+                CardEffect::DamageOverTime { .. } => Some(Element::Fire),
+                CardEffect::Stun { .. } => Some(Element::Air),
+                CardEffect::Heal(_) => Some(Element::Light),
+                CardEffect::Shield(_) => Some(Element::Earth),
+            }
+        }
+
+        /// Spells draw on the player's mana; plain attacks are free.
+        pub fn mana_cost(&self) -> u32 {
+            match self {
+                CardEffect::DirectDamage(_) => 0,
+                CardEffect::DamageOverTime { .. } => 8,
+                CardEffect::Stun { .. } => 12,
+                CardEffect::Heal(_) => 10,
+                CardEffect::Shield(_) => 8,
+            }
+        }
+
+        pub fn to_active_effect(&self) -> ActiveEffect {
+            match self {
+                CardEffect::DirectDamage(damage) => ActiveEffect::DirectDamage(*damage),
+                CardEffect::DamageOverTime {
+                    damage,
+                    duration,
+                    frequency,
+                } => ActiveEffect::DamageOverTime {
+                    damage: *damage,
+                    duration: Timer::from_seconds(*duration, TimerMode::Once),
+                    frequency: Timer::from_seconds(*frequency, TimerMode::Repeating),
+                },
+                CardEffect::Stun { duration } => ActiveEffect::Stun {
+                    duration: Timer::from_seconds(*duration, TimerMode::Once),
+                },
+                CardEffect::Heal(heal) => ActiveEffect::Heal(*heal),
+                CardEffect::Shield(shield) => ActiveEffect::Shield(*shield),
+            }
+        }
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                CardEffect::DirectDamage(_) => "Direct Damage",
+                CardEffect::DamageOverTime { .. } => "Damage Over Time",
+                CardEffect::Stun { .. } => "Stun",
+                CardEffect::Heal(_) => "Heal",
+                CardEffect::Shield(_) => "Shield",
+            }
+        }
+
+        /// A stronger version of this card, used when upgrading at a rest site.
+        pub fn upgraded(&self) -> CardEffect {
+            match self {
+                CardEffect::DirectDamage(damage) => {
+                    CardEffect::DirectDamage(damage + (damage * 3 / 10).max(3))
+                }
+                CardEffect::DamageOverTime {
+                    damage,
+                    duration,
+                    frequency,
+                } => CardEffect::DamageOverTime {
+                    damage: damage + 2,
+                    duration: duration + 1.0,
+                    frequency: *frequency,
+                },
+                CardEffect::Stun { duration } => CardEffect::Stun {
+                    duration: duration + 0.5,
+                },
+                CardEffect::Heal(heal) => CardEffect::Heal(heal + (heal * 3 / 10).max(3)),
+                CardEffect::Shield(shield) => CardEffect::Shield(shield + (shield * 3 / 10).max(3)),
+            }
+        }
+
+        pub fn describe(&self) -> String {
+            match self {
+                CardEffect::DirectDamage(damage) => format!("Deal {} damage", damage),
+                CardEffect::DamageOverTime {
+                    damage,
+                    duration,
+                    frequency,
+                } => format!(
+                    "Deal {} damage every {:.1}s for {:.1}s",
+                    damage, frequency, duration
+                ),
+                CardEffect::Stun { duration } => format!("Stun for {:.1}s", duration),
+                CardEffect::Heal(heal) => format!("Heal {}", heal),
+                CardEffect::Shield(shield) => format!("Gain {} shield", shield),
+            }
+        }
+
         pub fn get_sprite_path(&self) -> String {
             match self {
                 CardEffect::DirectDamage(_) => "direct.png".to_string(),
                 CardEffect::DamageOverTime { .. } => "dot.png".to_string(),
                 CardEffect::Stun { .. } => "stun.png".to_string(),
-                CardEffect::Heal(_) => "heal.png".to_string(),
+                CardEffect::Heal(_) | CardEffect::Shield(_) => "heal.png".to_string(),
             }
         }
     }
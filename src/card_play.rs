@@ -0,0 +1,360 @@
+pub mod card_play {
+    use bevy::{ecs::system::SystemParam, prelude::*};
+
+    use crate::card::card::CardEffect;
+    use crate::combat::combat::{
+        play_card, CombatEvent, Combatant, CombatantEffects, CombatantStats,
+    };
+    use crate::input::input::{Action, ActionInput, InputBindings, CARD_HOTKEYS};
+    use crate::layout::layout::ScreenAnchor;
+    use crate::mana::mana::Mana;
+    use crate::rng::rng::RunRng;
+    use crate::{
+        CardAnimation, CardAnimationState, CardAttackTimer, EnemyEntity, PlayerCard, PlayerEntity,
+    };
+
+    pub const READY_TO_PLAY_COLOR: Color = Color::srgb(1.0, 0.95, 0.5);
+    const TARGET_HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 0.6, 0.6);
+    const DROP_HIGHLIGHT_COLOR: Color = Color::srgb(0.6, 1.0, 0.6);
+    /// Lifts a dragged card above the other cards in hand.
+    const DRAG_Z_OFFSET: f32 = 0.5;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum PlayMode {
+        /// Cards fire on their own as soon as they are charged.
+        #[default]
+        Auto,
+        /// Charged cards wait for the player to click them.
+        Manual,
+    }
+
+    /// Whether cards play themselves, and whether attacks need a target picked by hand.
+    #[derive(Resource, Default)]
+    pub struct CardPlaySettings {
+        pub mode: PlayMode,
+        /// In manual mode, cards aimed at the opponent wait for a click on the enemy too.
+        pub pick_targets: bool,
+    }
+
+    /// Marks a charged card that is waiting for the player to play it.
+    #[derive(Component)]
+    pub struct ReadyToPlay;
+
+    /// The card that was clicked and still needs a target.
+    #[derive(Resource, Default)]
+    pub struct PendingTarget(pub Option<Entity>);
+
+    /// Marks a ready card that is being dragged towards a target.
+    #[derive(Component)]
+    pub struct DraggedCard;
+
+    #[derive(Component)]
+    pub struct PlayModeText;
+
+    /// Everything needed to play one of the player's ready cards by hand.
+    #[derive(SystemParam)]
+    pub struct PlayerCardPlay<'w, 's> {
+        commands: Commands<'w, 's>,
+        cards: Query<
+            'w,
+            's,
+            (
+                &'static CardEffect,
+                &'static mut CardAttackTimer,
+                &'static mut CardAnimation,
+                &'static mut Sprite,
+            ),
+            (With<PlayerCard>, With<ReadyToPlay>),
+        >,
+        mana: Query<'w, 's, &'static mut Mana, With<PlayerEntity>>,
+        stats: CombatantStats<'w, 's>,
+        rng: ResMut<'w, RunRng>,
+        effects: CombatantEffects<'w, 's>,
+        combat_events: EventWriter<'w, CombatEvent>,
+    }
+
+    impl PlayerCardPlay<'_, '_> {
+        pub fn ready_effect(&self, card: Entity) -> Option<&CardEffect> {
+            self.cards.get(card).ok().map(|(effect, ..)| effect)
+        }
+
+        /// Plays a ready card and starts charging it again. Returns false if the card
+        /// couldn't be played right now, e.g. while stunned or short on mana.
+        pub fn play(&mut self, card: Entity) -> bool {
+            if self.effects.is_stunned(Combatant::Player) {
+                return false;
+            }
+            let Ok((effect, mut timer, mut animation, mut sprite)) = self.cards.get_mut(card)
+            else {
+                return false;
+            };
+            let Ok(mut mana) = self.mana.get_single_mut() else {
+                return false;
+            };
+            let cost = effect.mana_cost();
+            if !mana.can_afford(cost) {
+                println!("[card_play] Not enough mana for {}", effect.name());
+                return false;
+            }
+            mana.spend(cost);
+
+            timer.0.reset();
+            sprite.color = Color::WHITE;
+            animation.state = CardAnimationState::MovingUp;
+            self.commands.entity(card).remove::<ReadyToPlay>();
+
+            play_card(
+                Combatant::Player,
+                effect,
+                &self.stats,
+                &mut self.rng,
+                &mut self.effects,
+                &mut self.combat_events,
+            );
+            true
+        }
+    }
+
+    /// Left-clicking a ready card plays it, or picks it up for targeting if the player
+    /// chose to aim their attacks.
+    pub fn play_card_on(
+    ) -> impl Fn(Trigger<Pointer<Click>>, Res<CardPlaySettings>, ResMut<PendingTarget>, PlayerCardPlay)
+    {
+        move |ev, settings, mut pending, mut card_play| {
+            if ev.event().button != PointerButton::Primary {
+                return;
+            }
+            select_card(ev.entity(), &settings, &mut pending, &mut card_play);
+        }
+    }
+
+    fn select_card(
+        card: Entity,
+        settings: &CardPlaySettings,
+        pending: &mut PendingTarget,
+        card_play: &mut PlayerCardPlay,
+    ) {
+        let Some(effect) = card_play.ready_effect(card) else {
+            return;
+        };
+        if settings.pick_targets && !effect.targets_self() {
+            // Selecting the picked card again puts it back down
+            pending.0 = if pending.0 == Some(card) {
+                None
+            } else {
+                Some(card)
+            };
+            return;
+        }
+        pending.0 = None;
+        card_play.play(card);
+    }
+
+    /// The number keys play the cards in hand from left to right, like clicking them.
+    pub fn play_card_hotkeys(
+        actions: Res<ActionInput>,
+        settings: Res<CardPlaySettings>,
+        mut pending: ResMut<PendingTarget>,
+        card_query: Query<(Entity, &CardAnimation), With<PlayerCard>>,
+        mut card_play: PlayerCardPlay,
+    ) {
+        let Some(index) =
+            (0..CARD_HOTKEYS).find(|index| actions.just_pressed(Action::PlayCard(*index)))
+        else {
+            return;
+        };
+        let mut cards: Vec<(Entity, f32)> = card_query
+            .iter()
+            .map(|(card, animation)| (card, animation.start_pos.x))
+            .collect();
+        cards.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((card, _)) = cards.get(index) {
+            select_card(*card, &settings, &mut pending, &mut card_play);
+        }
+    }
+
+    pub fn target_enemy_on(
+    ) -> impl Fn(Trigger<Pointer<Click>>, ResMut<PendingTarget>, PlayerCardPlay) {
+        move |ev, mut pending, mut card_play| {
+            if ev.event().button != PointerButton::Primary {
+                return;
+            }
+            if let Some(card) = pending.0.take() {
+                card_play.play(card);
+            }
+        }
+    }
+
+    /// Ready cards can be picked up and dropped onto whoever they should land on.
+    pub fn drag_card_start_on() -> impl Fn(
+        Trigger<Pointer<DragStart>>,
+        Query<&mut Transform, (With<PlayerCard>, With<ReadyToPlay>)>,
+        ResMut<PendingTarget>,
+        Commands,
+    ) {
+        move |ev, mut card_query, mut pending, mut commands| {
+            if ev.event().button != PointerButton::Primary {
+                return;
+            }
+            let Ok(mut transform) = card_query.get_mut(ev.entity()) else {
+                return;
+            };
+            transform.translation.z += DRAG_Z_OFFSET;
+            pending.0 = None;
+            // The card follows the pointer, so let whatever is underneath receive the drop
+            commands
+                .entity(ev.entity())
+                .insert((DraggedCard, PickingBehavior::IGNORE));
+        }
+    }
+
+    pub fn drag_card_on(
+    ) -> impl Fn(Trigger<Pointer<Drag>>, Query<&mut Transform, With<DraggedCard>>) {
+        move |ev, mut card_query| {
+            let Ok(mut transform) = card_query.get_mut(ev.entity()) else {
+                return;
+            };
+            // Screen space grows downwards, the world grows upwards
+            let delta = ev.event().delta;
+            transform.translation.x += delta.x;
+            transform.translation.y -= delta.y;
+        }
+    }
+
+    /// Wherever the card was let go, it goes back to its place in hand. A valid drop has
+    /// already played it by then.
+    pub fn drag_card_end_on() -> impl Fn(
+        Trigger<Pointer<DragEnd>>,
+        Query<(&mut Transform, &CardAnimation), With<DraggedCard>>,
+        Commands,
+    ) {
+        move |ev, mut card_query, mut commands| {
+            let Ok((mut transform, animation)) = card_query.get_mut(ev.entity()) else {
+                return;
+            };
+            transform.translation = animation.start_pos;
+            commands
+                .entity(ev.entity())
+                .remove::<(DraggedCard, PickingBehavior)>();
+        }
+    }
+
+    fn is_valid_target(effect: &CardEffect, target_is_player: bool) -> bool {
+        effect.targets_self() == target_is_player
+    }
+
+    /// Attached to the player and the enemy; plays the dropped card if it was meant for them.
+    pub fn drop_card_on() -> impl Fn(
+        Trigger<Pointer<DragDrop>>,
+        Query<(), With<DraggedCard>>,
+        Query<(), With<PlayerEntity>>,
+        PlayerCardPlay,
+    ) {
+        move |ev, dragged_query, player_query, mut card_play| {
+            let card = ev.event().dropped;
+            if !dragged_query.contains(card) {
+                return;
+            }
+            let Some(effect) = card_play.ready_effect(card) else {
+                return;
+            };
+            if is_valid_target(effect, player_query.contains(ev.entity())) {
+                card_play.play(card);
+            }
+        }
+    }
+
+    pub fn spawn_play_mode_text(mut commands: Commands) {
+        commands.spawn((
+            Name::new("Play Mode Text"),
+            Text2d::new(""),
+            TextFont::from_font_size(12.0),
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Transform::from_xyz(0.0, 0.0, 0.95),
+            ScreenAnchor::new(Vec2::ONE, Vec2::new(-70.0, -75.0)),
+            PlayModeText,
+        ));
+    }
+
+    pub fn toggle_play_mode(
+        actions: Res<ActionInput>,
+        bindings: Res<InputBindings>,
+        mut settings: ResMut<CardPlaySettings>,
+        new_text: Query<(), Added<PlayModeText>>,
+        mut text_query: Query<&mut Text2d, With<PlayModeText>>,
+    ) {
+        if actions.just_pressed(Action::TogglePlayMode) {
+            settings.mode = match settings.mode {
+                PlayMode::Auto => PlayMode::Manual,
+                PlayMode::Manual => PlayMode::Auto,
+            };
+        }
+        if actions.just_pressed(Action::ToggleAiming) {
+            settings.pick_targets = !settings.pick_targets;
+        }
+        if !settings.is_changed() && !bindings.is_changed() && new_text.is_empty() {
+            return;
+        }
+        let mode = match (settings.mode, settings.pick_targets) {
+            (PlayMode::Auto, _) => "Auto play",
+            (PlayMode::Manual, false) => "Manual play",
+            (PlayMode::Manual, true) => "Manual play, aimed",
+        };
+        for mut text in text_query.iter_mut() {
+            text.0 = format!(
+                "{} ({}/{})",
+                mode,
+                bindings.key_label(Action::TogglePlayMode),
+                bindings.key_label(Action::ToggleAiming)
+            );
+        }
+    }
+
+    /// Right-clicking or cancelling drops the picked card, and so does the card going away
+    /// or firing.
+    pub fn cancel_pending_target(
+        mouse: Res<ButtonInput<MouseButton>>,
+        actions: Res<ActionInput>,
+        mut pending: ResMut<PendingTarget>,
+        ready_query: Query<(), With<ReadyToPlay>>,
+    ) {
+        let Some(card) = pending.0 else {
+            return;
+        };
+        if mouse.just_pressed(MouseButton::Right)
+            || actions.just_pressed(Action::Cancel)
+            || !ready_query.contains(card)
+        {
+            pending.0 = None;
+        }
+    }
+
+    /// Tints the enemy while a picked card is waiting for a target, and whoever a dragged
+    /// card can be dropped on. Sprites are only touched when the tint changes, so other tints
+    /// aren't painted over every frame.
+    pub fn update_target_highlight(
+        pending: Res<PendingTarget>,
+        dragged_query: Query<&CardEffect, With<DraggedCard>>,
+        mut enemy_query: Query<&mut Sprite, (With<EnemyEntity>, Without<PlayerEntity>)>,
+        mut player_query: Query<&mut Sprite, (With<PlayerEntity>, Without<EnemyEntity>)>,
+        mut last_highlight: Local<Option<(Color, Color)>>,
+    ) {
+        let dragged = dragged_query.get_single().ok();
+        let highlight = |target_is_player: bool| match dragged {
+            Some(effect) if is_valid_target(effect, target_is_player) => DROP_HIGHLIGHT_COLOR,
+            None if !target_is_player && pending.0.is_some() => TARGET_HIGHLIGHT_COLOR,
+            _ => Color::WHITE,
+        };
+        let (enemy_color, player_color) = (highlight(false), highlight(true));
+        if *last_highlight == Some((enemy_color, player_color)) {
+            return;
+        }
+        *last_highlight = Some((enemy_color, player_color));
+        for mut sprite in enemy_query.iter_mut() {
+            sprite.color = enemy_color;
+        }
+        for mut sprite in player_query.iter_mut() {
+            sprite.color = player_color;
+        }
+    }
+}
@@ -0,0 +1,154 @@
+pub mod card_reward {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+
+    use crate::card::card::{CardEffect, Deck};
+    use crate::dungeon::dungeon::Dungeon;
+    use crate::focus::focus::Focusable;
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::loot::loot::pick_weighted;
+    use crate::skills::skills::Class;
+    use crate::{recolor_node_on, GameState, LootRarity, PlayerEntity};
+
+    const CARD_CHOICES: usize = 3;
+    const CARD_SIZE: f32 = 96.0;
+    const CARD_SPACING: f32 = 180.0;
+
+    #[derive(Component)]
+    pub struct CardRewardScreen;
+
+    fn rarity_weight(rarity: &LootRarity) -> u32 {
+        match rarity {
+            LootRarity::Common => 60,
+            LootRarity::Rare => 30,
+            LootRarity::Epic => 10,
+        }
+    }
+
+    /// Picks distinct cards from the class pool, favouring the more common ones.
+    fn roll_card_choices(class: &Class) -> Vec<(CardEffect, LootRarity)> {
+        let mut pool = class.card_pool();
+        let mut choices = vec![];
+        while choices.len() < CARD_CHOICES {
+            let Some(index) = pick_weighted(&(0..pool.len()).collect::<Vec<_>>(), |index| {
+                rarity_weight(&pool[*index].1)
+            })
+            .copied() else {
+                break;
+            };
+            choices.push(pool.swap_remove(index));
+        }
+        choices
+    }
+
+    pub fn spawn_card_reward(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        class_query: Query<&Class, With<PlayerEntity>>,
+    ) {
+        let class = class_query.get_single().unwrap_or(&Class::Warrior);
+        let choices = roll_card_choices(class);
+
+        commands
+            .spawn((
+                Name::new("Card Reward Screen"),
+                screen_node(Color::srgb(0.1, 0.1, 0.2)),
+                CardRewardScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((Text::new("Choose a card"), TextColor(Color::WHITE)));
+
+                parent
+                    .spawn(Node {
+                        align_items: AlignItems::FlexStart,
+                        margin: UiRect::vertical(Val::Px(30.0)),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        for (i, (effect, rarity)) in choices.into_iter().enumerate() {
+                            spawn_card_choice(parent, &asset_server, i, effect, rarity);
+                        }
+                    });
+
+                parent
+                    .spawn((
+                        Name::new("Skip Button"),
+                        box_node(Vec2::new(120.0, 40.0), Color::srgb(0.4, 0.4, 0.4)),
+                    ))
+                    .with_child(label_node("Skip", 16.0, Color::WHITE))
+                    .observe(recolor_node_on::<Pointer<Over>>(Color::srgb(0.6, 0.6, 0.6)))
+                    .observe(recolor_node_on::<Pointer<Out>>(Color::srgb(0.4, 0.4, 0.4)))
+                    .insert(Focusable)
+                    .observe(skip_card_reward_on::<Pointer<Click>>());
+            });
+    }
+
+    fn spawn_card_choice(
+        parent: &mut ChildBuilder,
+        asset_server: &AssetServer,
+        index: usize,
+        effect: CardEffect,
+        rarity: LootRarity,
+    ) {
+        parent
+            .spawn(Node {
+                width: Val::Px(CARD_SPACING),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn((
+                        Name::new(format!("Card Choice {}", index)),
+                        ImageNode::new(asset_server.load(effect.get_sprite_path())),
+                        Node {
+                            width: Val::Px(CARD_SIZE),
+                            height: Val::Px(CARD_SIZE),
+                            ..default()
+                        },
+                    ))
+                    .insert(Focusable)
+                    .observe(choose_card_on::<Pointer<Click>>(effect.clone()));
+                parent.spawn((Text::new(effect.name()), TextColor(rarity.get_color())));
+                parent.spawn((
+                    Text::new(effect.describe()),
+                    TextFont::from_font_size(12.0),
+                    TextColor(Color::WHITE),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    Node {
+                        max_width: Val::Px(CARD_SPACING - 20.0),
+                        ..default()
+                    },
+                ));
+            });
+    }
+
+    pub fn despawn_card_reward(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<CardRewardScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    fn choose_card_on<E: Debug + Clone + Reflect>(
+        effect: CardEffect,
+    ) -> impl Fn(Trigger<E>, (ResMut<Deck>, Res<Dungeon>, ResMut<NextState<GameState>>)) {
+        move |_ev, (mut deck, dungeon, mut next_state)| {
+            println!("[choose_card_on] Adding {} to deck", effect.describe());
+            deck.cards.push(effect.clone());
+            next_state.set(dungeon.roll_next_node().state());
+        }
+    }
+
+    fn skip_card_reward_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, (Res<Dungeon>, ResMut<NextState<GameState>>)) {
+        move |_ev, (dungeon, mut next_state)| {
+            next_state.set(dungeon.roll_next_node().state());
+        }
+    }
+}
@@ -0,0 +1,201 @@
+pub mod card_tooltip {
+    use std::fmt::Debug;
+
+    use bevy::{prelude::*, sprite::Anchor, text::TextBounds};
+
+    use crate::card::card::CardEffect;
+    use crate::layout::layout::{FullScreen, ScreenAnchor};
+    use crate::{CardAttackTimer, GameConfig};
+
+    const TOOLTIP_WIDTH: f32 = 170.0;
+    const TOOLTIP_HEIGHT: f32 = 86.0;
+    const TOOLTIP_OFFSET: f32 = 40.0;
+    const TOOLTIP_Z_LAYER: f32 = 0.95;
+    const INSPECT_Z_LAYER: f32 = 1.05;
+    const INSPECT_CARD_SIZE: f32 = 160.0;
+
+    #[derive(Component)]
+    pub struct CardTooltip;
+
+    #[derive(Component)]
+    pub struct CardInspectView;
+
+    fn card_details(effect: &CardEffect, cooldown: f32) -> String {
+        let element = effect
+            .element()
+            .map(|element| element.name())
+            .unwrap_or("Physical");
+        let cost = match effect.mana_cost() {
+            0 => "Free".to_string(),
+            cost => format!("{} mana", cost),
+        };
+        format!(
+            "{}\nCooldown: {:.1}s\nElement: {}\nCost: {}",
+            effect.describe(),
+            cooldown,
+            element,
+            cost
+        )
+    }
+
+    pub fn hover_card_on<E: Debug + Clone + Reflect>() -> impl Fn(
+        Trigger<E>,
+        (
+            Query<(&CardEffect, &CardAttackTimer, &GlobalTransform)>,
+            Query<(), With<CardInspectView>>,
+        ),
+        Commands,
+    ) {
+        move |ev, (card_query, inspect_query), mut commands| {
+            if !inspect_query.is_empty() {
+                return;
+            }
+            let Ok((effect, timer, transform)) = card_query.get(ev.entity()) else {
+                return;
+            };
+            // Cards near the top of the screen belong to the enemy, so show theirs below
+            let position = transform.translation();
+            let y = if position.y > 0.0 {
+                position.y - TOOLTIP_OFFSET - TOOLTIP_HEIGHT / 2.0
+            } else {
+                position.y + TOOLTIP_OFFSET + TOOLTIP_HEIGHT / 2.0
+            };
+
+            commands
+                .spawn((
+                    Name::new("Card Tooltip"),
+                    Sprite {
+                        color: Color::srgba(0.05, 0.05, 0.1, 0.9),
+                        custom_size: Some(Vec2::new(TOOLTIP_WIDTH, TOOLTIP_HEIGHT)),
+                        ..default()
+                    },
+                    Transform::from_xyz(position.x, y, TOOLTIP_Z_LAYER),
+                    PickingBehavior::IGNORE,
+                    CardTooltip,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text2d::new(effect.name()),
+                        TextFont::from_font_size(14.0),
+                        TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                        Anchor::TopLeft,
+                        Transform::from_xyz(
+                            -TOOLTIP_WIDTH / 2.0 + 6.0,
+                            TOOLTIP_HEIGHT / 2.0 - 4.0,
+                            0.1,
+                        ),
+                    ));
+                    parent.spawn((
+                        Text2d::new(card_details(effect, timer.duration().as_secs_f32())),
+                        TextFont::from_font_size(11.0),
+                        TextColor(Color::WHITE),
+                        TextBounds::new_horizontal(TOOLTIP_WIDTH - 12.0),
+                        Anchor::TopLeft,
+                        Transform::from_xyz(
+                            -TOOLTIP_WIDTH / 2.0 + 6.0,
+                            TOOLTIP_HEIGHT / 2.0 - 22.0,
+                            0.1,
+                        ),
+                    ));
+                });
+        }
+    }
+
+    pub fn hover_card_out<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, Query<Entity, With<CardTooltip>>, Commands) {
+        move |_ev, tooltip_query, mut commands| {
+            for tooltip in tooltip_query.iter() {
+                commands.entity(tooltip).despawn_recursive();
+            }
+        }
+    }
+
+    /// Right-clicking a card opens a larger view of it; clicking anywhere closes it again.
+    pub fn inspect_card_on() -> impl Fn(
+        Trigger<Pointer<Click>>,
+        (
+            Query<(&CardEffect, &CardAttackTimer)>,
+            Query<Entity, Or<(With<CardInspectView>, With<CardTooltip>)>>,
+            Res<AssetServer>,
+            Res<GameConfig>,
+        ),
+        Commands,
+    ) {
+        move |ev, (card_query, open_query, asset_server, game_config), mut commands| {
+            if ev.event().button != PointerButton::Secondary {
+                return;
+            }
+            let Ok((effect, timer)) = card_query.get(ev.entity()) else {
+                return;
+            };
+            for open in open_query.iter() {
+                commands.entity(open).despawn_recursive();
+            }
+
+            commands
+                .spawn((
+                    Name::new("Card Inspect View"),
+                    Sprite {
+                        color: Color::srgba(0.0, 0.0, 0.0, 0.8),
+                        ..default()
+                    },
+                    Transform::from_xyz(0.0, 0.0, INSPECT_Z_LAYER),
+                    FullScreen,
+                    CardInspectView,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Sprite {
+                            image: asset_server.load(effect.get_sprite_path()),
+                            custom_size: Some(Vec2::splat(INSPECT_CARD_SIZE)),
+                            ..default()
+                        },
+                        Transform::from_xyz(-INSPECT_CARD_SIZE / 2.0 - 20.0, 0.0, 0.1),
+                        PickingBehavior::IGNORE,
+                    ));
+                    parent.spawn((
+                        Text2d::new(effect.name()),
+                        TextFont::from_font_size(22.0),
+                        TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                        Anchor::BottomLeft,
+                        Transform::from_xyz(20.0, 30.0, 0.1),
+                    ));
+                    parent.spawn((
+                        Text2d::new(card_details(effect, timer.duration().as_secs_f32())),
+                        TextFont::from_font_size(15.0),
+                        TextColor(Color::WHITE),
+                        TextBounds::new_horizontal(game_config.screen_width / 2.0 - 40.0),
+                        Anchor::TopLeft,
+                        Transform::from_xyz(20.0, 20.0, 0.1),
+                    ));
+                    parent.spawn((
+                        Text2d::new("Click anywhere to close"),
+                        TextFont::from_font_size(12.0),
+                        TextColor(Color::srgb(0.6, 0.6, 0.6)),
+                        Transform::from_xyz(0.0, 0.0, 0.1),
+                        ScreenAnchor::bottom(30.0),
+                    ));
+                })
+                .observe(close_inspect_on::<Pointer<Click>>());
+        }
+    }
+
+    /// Cards can vanish mid-hover when a fight ends, which never sends `Out`.
+    pub fn despawn_card_tooltips(
+        mut commands: Commands,
+        open_query: Query<Entity, Or<(With<CardInspectView>, With<CardTooltip>)>>,
+    ) {
+        for open in open_query.iter() {
+            commands.entity(open).despawn_recursive();
+        }
+    }
+
+    fn close_inspect_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, Query<Entity, With<CardInspectView>>, Commands) {
+        move |_ev, inspect_query, mut commands| {
+            for view in inspect_query.iter() {
+                commands.entity(view).despawn_recursive();
+            }
+        }
+    }
+}
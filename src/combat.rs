@@ -0,0 +1,311 @@
+pub mod combat {
+    use bevy::{ecs::system::SystemParam, prelude::*};
+
+    use crate::card::card::{CardEffect, Effects};
+    use crate::rng::rng::RunRng;
+    use crate::skills::skills::Stats;
+    use crate::{EnemyEntity, PlayerEntity};
+
+    const BASE_MISS_CHANCE: f32 = 0.1;
+    const MISS_REDUCTION_PER_PERCEPTION: f32 = 0.005;
+    const DODGE_PER_AGILITY: f32 = 0.01;
+    const MAX_DODGE_CHANCE: f32 = 0.35;
+    const CRIT_PER_PERCEPTION: f32 = 0.01;
+    const MAX_CRIT_CHANCE: f32 = 0.5;
+    const CRIT_MULTIPLIER: f32 = 1.5;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Combatant {
+        Player,
+        Enemy,
+    }
+
+    impl Combatant {
+        pub fn opponent(&self) -> Combatant {
+            match self {
+                Combatant::Player => Combatant::Enemy,
+                Combatant::Enemy => Combatant::Player,
+            }
+        }
+
+        /// Who ends up holding the effect when `self` plays it.
+        pub fn effect_target(&self, effect: &CardEffect) -> Combatant {
+            if effect.targets_self() {
+                *self
+            } else {
+                self.opponent()
+            }
+        }
+    }
+
+    /// Everything that happens in a fight goes through here, so anything that wants to react
+    /// to combat (relics, UI, stats) reads these instead of poking at health components.
+    #[derive(Event, Clone, Debug)]
+    pub enum CombatEvent {
+        BattleStarted,
+        CardPlayed {
+            owner: Combatant,
+            effect: CardEffect,
+        },
+        DamageDealt {
+            target: Combatant,
+            amount: i32,
+            /// A damage-over-time tick rather than a card hitting.
+            over_time: bool,
+        },
+        Healed {
+            target: Combatant,
+            amount: i32,
+        },
+        Shielded {
+            target: Combatant,
+            amount: i32,
+        },
+        /// Damage the target's shield soaked up before it reached health.
+        Absorbed {
+            target: Combatant,
+            amount: i32,
+        },
+        StatusApplied {
+            target: Combatant,
+            effect: CardEffect,
+        },
+        /// An attack on `target` didn't connect; `dodged` if the target avoided it.
+        Missed {
+            target: Combatant,
+            dodged: bool,
+        },
+        CriticalHit {
+            target: Combatant,
+        },
+        StatusExpired {
+            target: Combatant,
+            name: &'static str,
+        },
+        EnemyKilled,
+        PlayerDied,
+        ItemUsed {
+            name: String,
+        },
+    }
+
+    /// Soaks up damage before it reaches health.
+    #[derive(Component, Default, Reflect)]
+    pub struct Shield(pub i32);
+
+    impl Shield {
+        /// Takes what it can out of `damage` and returns what gets through.
+        pub fn absorb(&mut self, damage: i32) -> i32 {
+            let absorbed = damage.clamp(0, self.0);
+            self.0 -= absorbed;
+            damage - absorbed
+        }
+    }
+
+    /// Runs a hit through the target's shield and takes the rest off `health`. A hit the
+    /// shield soaks up entirely only raises `Absorbed`, so nothing reacts to it as damage.
+    pub fn take_damage(
+        target: Combatant,
+        damage: i32,
+        over_time: bool,
+        health: &mut i32,
+        shield: &mut Shield,
+        combat_events: &mut EventWriter<CombatEvent>,
+    ) {
+        let dealt = shield.absorb(damage);
+        if dealt < damage {
+            combat_events.send(CombatEvent::Absorbed {
+                target,
+                amount: damage - dealt,
+            });
+        }
+        if dealt > 0 {
+            *health -= dealt;
+            combat_events.send(CombatEvent::DamageDealt {
+                target,
+                amount: dealt,
+                over_time,
+            });
+        }
+    }
+
+    #[derive(SystemParam)]
+    pub struct CombatantEffects<'w, 's> {
+        effects: ParamSet<
+            'w,
+            's,
+            (
+                Query<'w, 's, &'static mut Effects, With<PlayerEntity>>,
+                Query<'w, 's, &'static mut Effects, With<EnemyEntity>>,
+            ),
+        >,
+    }
+
+    impl CombatantEffects<'_, '_> {
+        pub fn is_stunned(&mut self, combatant: Combatant) -> bool {
+            let stunned = |effects: &Effects| effects.is_stunned();
+            match combatant {
+                Combatant::Player => self.effects.p0().get_single().map(stunned),
+                Combatant::Enemy => self.effects.p1().get_single().map(stunned),
+            }
+            .unwrap_or(false)
+        }
+
+        pub fn push(&mut self, target: Combatant, effect: &CardEffect) -> bool {
+            let active_effect = effect.to_active_effect();
+            let pushed = match target {
+                Combatant::Player => self
+                    .effects
+                    .p0()
+                    .get_single_mut()
+                    .map(|mut effects| effects.effects.push(active_effect)),
+                Combatant::Enemy => self
+                    .effects
+                    .p1()
+                    .get_single_mut()
+                    .map(|mut effects| effects.effects.push(active_effect)),
+            };
+            pushed.is_ok()
+        }
+    }
+
+    #[derive(SystemParam)]
+    pub struct CombatantStats<'w, 's> {
+        player: Query<'w, 's, &'static Stats, With<PlayerEntity>>,
+        enemy: Query<'w, 's, &'static Stats, With<EnemyEntity>>,
+    }
+
+    impl CombatantStats<'_, '_> {
+        pub fn get(&self, combatant: Combatant) -> Option<&Stats> {
+            match combatant {
+                Combatant::Player => self.player.get_single().ok(),
+                Combatant::Enemy => self.enemy.get_single().ok(),
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum HitOutcome {
+        Hit,
+        Crit,
+        Miss,
+        Dodge,
+    }
+
+    /// Perception makes attacks land and crit, agility lets the defender dodge.
+    pub fn roll_hit(attacker: &Stats, defender: &Stats, rng: &mut RunRng) -> HitOutcome {
+        let miss_chance = (BASE_MISS_CHANCE
+            - attacker.perception as f32 * MISS_REDUCTION_PER_PERCEPTION)
+            .max(0.0);
+        let dodge_chance = (defender.agility as f32 * DODGE_PER_AGILITY).min(MAX_DODGE_CHANCE);
+        let crit_chance = (attacker.perception as f32 * CRIT_PER_PERCEPTION).min(MAX_CRIT_CHANCE);
+
+        if rng.chance(miss_chance) {
+            HitOutcome::Miss
+        } else if rng.chance(dodge_chance) {
+            HitOutcome::Dodge
+        } else if rng.chance(crit_chance) {
+            HitOutcome::Crit
+        } else {
+            HitOutcome::Hit
+        }
+    }
+
+    /// Plays a card for `owner`. Attacks on the opponent roll to hit first; effects on
+    /// the owner always land.
+    pub fn play_card(
+        owner: Combatant,
+        effect: &CardEffect,
+        stats: &CombatantStats,
+        rng: &mut RunRng,
+        effects: &mut CombatantEffects,
+        combat_events: &mut EventWriter<CombatEvent>,
+    ) {
+        combat_events.send(CombatEvent::CardPlayed {
+            owner,
+            effect: effect.clone(),
+        });
+
+        let target = owner.effect_target(effect);
+        let (Some(attacker), Some(defender)) = (stats.get(owner), stats.get(target)) else {
+            apply_card_effect(owner, effect, effects, combat_events);
+            return;
+        };
+        if target == owner {
+            apply_card_effect(owner, effect, effects, combat_events);
+            return;
+        }
+
+        match roll_hit(attacker, defender, rng) {
+            HitOutcome::Hit => apply_card_effect(owner, effect, effects, combat_events),
+            HitOutcome::Crit => {
+                combat_events.send(CombatEvent::CriticalHit { target });
+                apply_card_effect(
+                    owner,
+                    &effect.critical(CRIT_MULTIPLIER),
+                    effects,
+                    combat_events,
+                );
+            }
+            HitOutcome::Miss => {
+                combat_events.send(CombatEvent::Missed {
+                    target,
+                    dodged: false,
+                });
+            }
+            HitOutcome::Dodge => {
+                combat_events.send(CombatEvent::Missed {
+                    target,
+                    dodged: true,
+                });
+            }
+        }
+    }
+
+    /// Resolves an effect played by `owner` onto whoever it targets. Cards, relics and
+    /// consumables all go through here so they behave the same way.
+    pub fn apply_card_effect(
+        owner: Combatant,
+        effect: &CardEffect,
+        effects: &mut CombatantEffects,
+        combat_events: &mut EventWriter<CombatEvent>,
+    ) {
+        let target = owner.effect_target(effect);
+        if !effects.push(target, effect) {
+            println!("[apply_card_effect] No {:?} found for {:?}", target, effect);
+            return;
+        }
+        if effect.is_status() {
+            combat_events.send(CombatEvent::StatusApplied {
+                target,
+                effect: effect.clone(),
+            });
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn roll_hit_repeats_for_the_same_seed() {
+            let attacker = Stats {
+                perception: 15,
+                ..default()
+            };
+            let defender = Stats {
+                agility: 20,
+                ..default()
+            };
+            let rolls = |seed: u64| {
+                let mut rng = RunRng::from_seed(seed);
+                (0..100)
+                    .map(|_| roll_hit(&attacker, &defender, &mut rng))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(rolls(42), rolls(42));
+            assert_ne!(rolls(42), rolls(43));
+        }
+    }
+}
@@ -0,0 +1,230 @@
+pub mod combat_log {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use bevy::{input::mouse::MouseWheel, prelude::*, sprite::Anchor, text::TextBounds};
+    use serde::Serialize;
+
+    use crate::combat::combat::CombatEvent;
+    use crate::dungeon::dungeon::Dungeon;
+    use crate::input::input::{Action, ActionInput};
+    use crate::layout::layout::ScreenAnchor;
+    use crate::rng::rng::RunRng;
+
+    const PANEL_SIZE: Vec2 = Vec2::new(210.0, 190.0);
+    const PANEL_PADDING: f32 = 6.0;
+    const VISIBLE_LINES: usize = 12;
+    const TRANSCRIPT_DIR: &str = "logs";
+
+    #[derive(Serialize, Clone)]
+    pub struct LogEntry {
+        /// Seconds since the battle started.
+        pub time: f32,
+        pub text: String,
+    }
+
+    /// Everything that happened in the current battle, oldest first.
+    #[derive(Resource, Default)]
+    pub struct CombatLog {
+        pub entries: Vec<LogEntry>,
+        battle_started_at: f32,
+        /// How many lines the panel is scrolled back from the newest entry.
+        scroll: usize,
+    }
+
+    #[derive(Serialize)]
+    struct Transcript<'a> {
+        seed: u64,
+        dungeon: &'a Dungeon,
+        entries: &'a [LogEntry],
+    }
+
+    #[derive(Component)]
+    pub struct CombatLogPanel;
+
+    #[derive(Component)]
+    pub struct CombatLogText;
+
+    fn describe_event(event: &CombatEvent) -> String {
+        match event {
+            CombatEvent::BattleStarted => "Battle started".to_string(),
+            CombatEvent::CardPlayed { owner, effect } => {
+                format!("{:?} played {}", owner, effect.name())
+            }
+            CombatEvent::DamageDealt {
+                target,
+                amount,
+                over_time: false,
+            } => format!("{:?} took {} damage", target, amount),
+            CombatEvent::DamageDealt {
+                target,
+                amount,
+                over_time: true,
+            } => format!("{:?} took {} tick damage", target, amount),
+            CombatEvent::Healed { target, amount } => format!("{:?} healed {}", target, amount),
+            CombatEvent::Shielded { target, amount } => {
+                format!("{:?} gained {} shield", target, amount)
+            }
+            CombatEvent::Absorbed { target, amount } => {
+                format!("{:?}'s shield absorbed {}", target, amount)
+            }
+            CombatEvent::StatusApplied { target, effect } => {
+                format!("{:?} afflicted: {}", target, effect.describe())
+            }
+            CombatEvent::StatusExpired { target, name } => {
+                format!("{:?}'s {} wore off", target, name)
+            }
+            CombatEvent::Missed {
+                target,
+                dodged: false,
+            } => format!("Attack on {:?} missed", target),
+            CombatEvent::Missed {
+                target,
+                dodged: true,
+            } => format!("{:?} dodged", target),
+            CombatEvent::CriticalHit { target } => format!("Critical hit on {:?}", target),
+            CombatEvent::EnemyKilled => "Enemy died".to_string(),
+            CombatEvent::PlayerDied => "Player died".to_string(),
+            CombatEvent::ItemUsed { name } => format!("Used {}", name),
+        }
+    }
+
+    pub fn record_combat_log(
+        time: Res<Time>,
+        mut combat_events: EventReader<CombatEvent>,
+        mut log: ResMut<CombatLog>,
+    ) {
+        for event in combat_events.read() {
+            if matches!(event, CombatEvent::BattleStarted) {
+                log.entries.clear();
+                log.scroll = 0;
+                log.battle_started_at = time.elapsed_secs();
+            }
+            let entry = LogEntry {
+                time: time.elapsed_secs() - log.battle_started_at,
+                text: describe_event(event),
+            };
+            log.entries.push(entry);
+        }
+    }
+
+    pub fn spawn_combat_log(mut commands: Commands) {
+        commands
+            .spawn((
+                Name::new("Combat Log"),
+                Sprite {
+                    color: Color::srgba(0.0, 0.0, 0.0, 0.6),
+                    custom_size: Some(PANEL_SIZE),
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 0.0, 0.85),
+                ScreenAnchor::new(Vec2::X, Vec2::new(-PANEL_SIZE.x / 2.0 - 10.0, 0.0)),
+                Visibility::Visible,
+                CombatLogPanel,
+            ))
+            .with_child((
+                Text2d::new(""),
+                TextFont::from_font_size(11.0),
+                TextColor(Color::srgb(0.85, 0.85, 0.85)),
+                TextBounds::new_horizontal(PANEL_SIZE.x - PANEL_PADDING * 2.0),
+                Anchor::TopLeft,
+                Transform::from_xyz(
+                    -PANEL_SIZE.x / 2.0 + PANEL_PADDING,
+                    PANEL_SIZE.y / 2.0 - PANEL_PADDING,
+                    0.1,
+                ),
+                CombatLogText,
+            ));
+    }
+
+    pub fn despawn_combat_log(
+        mut commands: Commands,
+        panel_query: Query<Entity, With<CombatLogPanel>>,
+    ) {
+        for panel in panel_query.iter() {
+            commands.entity(panel).despawn_recursive();
+        }
+    }
+
+    pub fn update_combat_log_panel(
+        log: Res<CombatLog>,
+        new_panel: Query<(), Added<CombatLogText>>,
+        mut text_query: Query<&mut Text2d, With<CombatLogText>>,
+    ) {
+        if !log.is_changed() && new_panel.is_empty() {
+            return;
+        }
+        let end = log.entries.len().saturating_sub(log.scroll);
+        let start = end.saturating_sub(VISIBLE_LINES);
+        let lines: Vec<String> = log.entries[start..end]
+            .iter()
+            .map(|entry| format!("[{:>5.1}] {}", entry.time, entry.text))
+            .collect();
+        for mut text in text_query.iter_mut() {
+            text.0 = lines.join("\n");
+        }
+    }
+
+    /// Hides or shows the panel, scrolls it with the mouse wheel or the log actions, and
+    /// saves the battle so far to a JSON file.
+    pub fn handle_combat_log_input(
+        actions: Res<ActionInput>,
+        mut wheel_events: EventReader<MouseWheel>,
+        mut log: ResMut<CombatLog>,
+        rng: Res<RunRng>,
+        dungeon: Res<Dungeon>,
+        mut panel_query: Query<&mut Visibility, With<CombatLogPanel>>,
+    ) {
+        if actions.just_pressed(Action::ToggleCombatLog) {
+            for mut visibility in panel_query.iter_mut() {
+                visibility.toggle_visible_hidden();
+            }
+        }
+
+        // The inventory screen has its own use for the wheel, so only scroll while the
+        // panel is up
+        let panel_shown = panel_query
+            .iter()
+            .any(|visibility| *visibility != Visibility::Hidden);
+        let mut scroll = log.scroll as i32;
+        for event in wheel_events.read() {
+            if panel_shown {
+                scroll += event.y.signum() as i32;
+            }
+        }
+        if actions.just_pressed(Action::ScrollLogUp) {
+            scroll += VISIBLE_LINES as i32;
+        }
+        if actions.just_pressed(Action::ScrollLogDown) {
+            scroll -= VISIBLE_LINES as i32;
+        }
+        let max_scroll = log.entries.len().saturating_sub(VISIBLE_LINES) as i32;
+        let scroll = scroll.clamp(0, max_scroll) as usize;
+        if scroll != log.scroll {
+            log.scroll = scroll;
+        }
+
+        if actions.just_pressed(Action::SaveTranscript) {
+            match save_transcript(&log, &rng, &dungeon) {
+                Ok(path) => println!("[combat_log] Saved transcript to {}", path),
+                Err(err) => println!("[combat_log] Could not save transcript: {}", err),
+            }
+        }
+    }
+
+    fn save_transcript(
+        log: &CombatLog,
+        rng: &RunRng,
+        dungeon: &Dungeon,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let transcript = Transcript {
+            seed: rng.seed,
+            dungeon,
+            entries: &log.entries,
+        };
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        std::fs::create_dir_all(TRANSCRIPT_DIR)?;
+        let path = format!("{}/battle_{}.json", TRANSCRIPT_DIR, timestamp);
+        std::fs::write(&path, serde_json::to_string_pretty(&transcript)?)?;
+        Ok(path)
+    }
+}
@@ -0,0 +1,166 @@
+pub mod consumables {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+
+    use crate::combat::combat::{apply_card_effect, CombatEvent, Combatant, CombatantEffects};
+    use crate::focus::focus::Focusable;
+    use crate::input::input::{Action, ActionInput, InputBindings};
+    use crate::layout::layout::ScreenAnchor;
+    use crate::Inventory;
+
+    pub const POTION_BELT_SLOTS: usize = 3;
+    const POTION_SLOT_SIZE: f32 = 50.0;
+    const EMPTY_SLOT_COLOR: Color = Color::srgba(0.2, 0.2, 0.2, 0.7);
+
+    #[derive(Component)]
+    pub struct PotionBelt;
+
+    #[derive(Component)]
+    pub struct PotionSlot(usize);
+
+    #[derive(Component)]
+    pub struct PotionSlotText;
+
+    /// Inventory indices of the consumables sitting in the belt, in slot order.
+    fn belt_items(inventory: &Inventory) -> Vec<usize> {
+        inventory
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.consumable_effect().is_some())
+            .map(|(index, _)| index)
+            .take(POTION_BELT_SLOTS)
+            .collect()
+    }
+
+    fn abbreviate(name: &str) -> String {
+        name.split_whitespace()
+            .filter_map(|word| word.chars().next())
+            .collect()
+    }
+
+    fn use_potion(
+        slot: usize,
+        inventory: &mut Inventory,
+        effects: &mut CombatantEffects,
+        combat_events: &mut EventWriter<CombatEvent>,
+    ) {
+        let Some(&index) = belt_items(inventory).get(slot) else {
+            return;
+        };
+        let item = inventory.items.remove(index);
+        let Some(effect) = item.consumable_effect() else {
+            return;
+        };
+        println!("[use_potion] Using {} from slot {}", item.name, slot);
+        combat_events.send(CombatEvent::ItemUsed { name: item.name });
+        apply_card_effect(Combatant::Player, &effect, effects, combat_events);
+    }
+
+    pub fn spawn_potion_belt(mut commands: Commands) {
+        commands
+            .spawn((
+                Name::new("Potion Belt"),
+                PotionBelt,
+                Transform::from_xyz(0.0, 0.0, 0.9),
+                ScreenAnchor::new(Vec2::NEG_ONE, Vec2::splat(POTION_SLOT_SIZE)),
+                Visibility::default(),
+            ))
+            .with_children(|parent| {
+                for slot in 0..POTION_BELT_SLOTS {
+                    parent
+                        .spawn((
+                            Name::new(format!("Potion Slot {}", slot)),
+                            Sprite {
+                                color: EMPTY_SLOT_COLOR,
+                                custom_size: Some(Vec2::splat(POTION_SLOT_SIZE)),
+                                ..default()
+                            },
+                            Transform::from_xyz(slot as f32 * (POTION_SLOT_SIZE + 10.0), 0.0, 0.0),
+                            PotionSlot(slot),
+                            Focusable,
+                        ))
+                        .with_child((
+                            Text2d::new(""),
+                            TextFont::from_font_size(14.0),
+                            Transform::from_xyz(0.0, 0.0, 0.1),
+                            PotionSlotText,
+                        ))
+                        .observe(use_potion_on::<Pointer<Click>>(slot));
+                }
+            });
+    }
+
+    pub fn despawn_potion_belt(
+        mut commands: Commands,
+        belt_query: Query<Entity, With<PotionBelt>>,
+    ) {
+        for belt in belt_query.iter() {
+            commands.entity(belt).despawn_recursive();
+        }
+    }
+
+    pub fn update_potion_belt(
+        inventory: Res<Inventory>,
+        bindings: Res<InputBindings>,
+        new_slots: Query<(), Added<PotionSlot>>,
+        mut slot_query: Query<(&PotionSlot, &mut Sprite, &Children)>,
+        mut text_query: Query<(&mut Text2d, &mut TextColor), With<PotionSlotText>>,
+    ) {
+        if !inventory.is_changed() && !bindings.is_changed() && new_slots.is_empty() {
+            return;
+        }
+
+        let belt = belt_items(&inventory);
+        for (slot, mut sprite, children) in slot_query.iter_mut() {
+            let item = belt.get(slot.0).map(|&index| &inventory.items[index]);
+            sprite.color = item.map_or(EMPTY_SLOT_COLOR, |item| item.rarity.get_color());
+
+            for child in children.iter() {
+                let Ok((mut text, mut text_color)) = text_query.get_mut(*child) else {
+                    continue;
+                };
+                let label = bindings.key_label(Action::UsePotion(slot.0));
+                match item {
+                    Some(item) => {
+                        text.0 = format!("{}\n{}", label, abbreviate(&item.name));
+                        text_color.0 = item.rarity.get_text_color();
+                    }
+                    None => {
+                        text.0 = label;
+                        text_color.0 = Color::WHITE;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn handle_potion_hotkeys(
+        actions: Res<ActionInput>,
+        mut inventory: ResMut<Inventory>,
+        mut effects: CombatantEffects,
+        mut combat_events: EventWriter<CombatEvent>,
+    ) {
+        for slot in 0..POTION_BELT_SLOTS {
+            if actions.just_pressed(Action::UsePotion(slot)) {
+                use_potion(slot, &mut inventory, &mut effects, &mut combat_events);
+            }
+        }
+    }
+
+    fn use_potion_on<E: Debug + Clone + Reflect>(
+        slot: usize,
+    ) -> impl Fn(
+        Trigger<E>,
+        (
+            ResMut<Inventory>,
+            CombatantEffects,
+            EventWriter<CombatEvent>,
+        ),
+    ) {
+        move |_ev, (mut inventory, mut effects, mut combat_events)| {
+            use_potion(slot, &mut inventory, &mut effects, &mut combat_events);
+        }
+    }
+}
@@ -0,0 +1,220 @@
+pub mod controls {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+
+    use crate::focus::focus::Focusable;
+    use crate::input::input::{key_name, Action, Binding, InputBindings, Rebinding};
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::{recolor_node_on, GameState};
+
+    const ROW_SIZE: Vec2 = Vec2::new(290.0, 20.0);
+    const ROW_GAP: f32 = 4.0;
+    const COLUMN_GAP: f32 = 14.0;
+    const BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.4);
+    const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.35, 0.6);
+    const LISTENING_COLOR: Color = Color::srgb(0.6, 0.5, 0.2);
+
+    #[derive(Component)]
+    pub struct ControlsScreen;
+
+    #[derive(Component)]
+    pub struct ControlsContent;
+
+    fn describe_binding(binding: &Binding) -> String {
+        let keys: Vec<String> = binding.keys.iter().map(|key| key_name(*key)).collect();
+        let buttons: Vec<String> = binding
+            .buttons
+            .iter()
+            .map(|button| format!("{:?}", button))
+            .collect();
+        let keys = if keys.is_empty() {
+            "-".to_string()
+        } else {
+            keys.join(", ")
+        };
+        if buttons.is_empty() {
+            keys
+        } else {
+            format!("{} / {}", keys, buttons.join(", "))
+        }
+    }
+
+    fn spawn_button<'a>(
+        parent: &'a mut ChildBuilder,
+        size: Vec2,
+        color: Color,
+        label: String,
+    ) -> EntityCommands<'a> {
+        let mut button = parent.spawn((box_node(size, color), Focusable));
+        button
+            .with_child(label_node(label, 12.0, Color::WHITE))
+            .observe(recolor_node_on::<Pointer<Over>>(BUTTON_HOVER_COLOR))
+            .observe(recolor_node_on::<Pointer<Out>>(color));
+        button
+    }
+
+    pub fn spawn_controls_screen(mut commands: Commands) {
+        commands
+            .spawn((
+                Name::new("Controls Screen"),
+                screen_node(Color::srgb(0.05, 0.05, 0.1)),
+                ControlsScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new("Controls"),
+                    TextFont::from_font_size(24.0),
+                    TextColor(Color::WHITE),
+                ));
+                parent.spawn((
+                    Text::new(
+                        "Pick an action, then press a key or gamepad button. Escape cancels.",
+                    ),
+                    TextFont::from_font_size(11.0),
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ));
+                parent.spawn((
+                    Name::new("Controls Content"),
+                    Node {
+                        column_gap: Val::Px(COLUMN_GAP),
+                        margin: UiRect::vertical(Val::Px(10.0)),
+                        ..default()
+                    },
+                    ControlsContent,
+                ));
+                parent
+                    .spawn(Node {
+                        column_gap: Val::Px(20.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        let size = Vec2::new(120.0, 28.0);
+                        spawn_button(parent, size, BUTTON_COLOR, "Defaults".to_string())
+                            .observe(reset_bindings_on::<Pointer<Click>>());
+                        spawn_button(parent, size, BUTTON_COLOR, "Back".to_string())
+                            .observe(back_to_settings_on::<Pointer<Click>>());
+                    });
+            });
+    }
+
+    pub fn despawn_controls_screen(
+        mut commands: Commands,
+        mut rebinding: ResMut<Rebinding>,
+        screen_query: Query<Entity, With<ControlsScreen>>,
+    ) {
+        rebinding.0 = None;
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    /// Rebuilds the list whenever a binding changes or an action starts listening.
+    pub fn refresh_controls_screen(
+        mut commands: Commands,
+        bindings: Res<InputBindings>,
+        rebinding: Res<Rebinding>,
+        new_screen: Query<(), Added<ControlsContent>>,
+        content_query: Query<Entity, With<ControlsContent>>,
+    ) {
+        if !bindings.is_changed() && !rebinding.is_changed() && new_screen.is_empty() {
+            return;
+        }
+        let Ok(content) = content_query.get_single() else {
+            return;
+        };
+
+        let per_column = bindings.bindings.len().div_ceil(2);
+        commands.entity(content).despawn_descendants();
+        commands.entity(content).with_children(|parent| {
+            for column in bindings.bindings.chunks(per_column.max(1)) {
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(ROW_GAP),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        for binding in column {
+                            let (color, value) = if rebinding.0 == Some(binding.action) {
+                                (LISTENING_COLOR, "press a key...".to_string())
+                            } else {
+                                (BUTTON_COLOR, describe_binding(binding))
+                            };
+                            spawn_button(
+                                parent,
+                                ROW_SIZE,
+                                color,
+                                format!("{}: {}", binding.action.name(), value),
+                            )
+                            .observe(start_rebinding_on::<
+                                Pointer<Click>,
+                            >(
+                                binding.action
+                            ));
+                        }
+                    });
+            }
+        });
+    }
+
+    /// Takes the next key or gamepad button as the new binding for the listening action.
+    pub fn capture_rebinding(
+        keys: Res<ButtonInput<KeyCode>>,
+        gamepads: Query<&Gamepad>,
+        mut rebinding: ResMut<Rebinding>,
+        mut bindings: ResMut<InputBindings>,
+    ) {
+        // Skip the frame it started listening, or the confirm press would bind itself
+        if rebinding.is_changed() {
+            return;
+        }
+        let Some(action) = rebinding.0 else {
+            return;
+        };
+        if keys.just_pressed(KeyCode::Escape) {
+            rebinding.0 = None;
+            return;
+        }
+
+        if let Some(key) = keys.get_just_pressed().next() {
+            bindings.rebind_key(action, *key);
+        } else if let Some(button) = gamepads
+            .iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next())
+        {
+            bindings.rebind_button(action, *button);
+        } else {
+            return;
+        }
+        rebinding.0 = None;
+        if let Err(err) = bindings.save() {
+            println!("[controls] Could not save bindings: {}", err);
+        }
+    }
+
+    fn start_rebinding_on<E: Debug + Clone + Reflect>(
+        action: Action,
+    ) -> impl Fn(Trigger<E>, ResMut<Rebinding>) {
+        move |_ev, mut rebinding| {
+            rebinding.0 = Some(action);
+        }
+    }
+
+    fn reset_bindings_on<E: Debug + Clone + Reflect>() -> impl Fn(Trigger<E>, ResMut<InputBindings>)
+    {
+        move |_ev, mut bindings| {
+            *bindings = InputBindings::default();
+            if let Err(err) = bindings.save() {
+                println!("[controls] Could not save bindings: {}", err);
+            }
+        }
+    }
+
+    fn back_to_settings_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::Settings);
+        }
+    }
+}
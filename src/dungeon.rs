@@ -0,0 +1,154 @@
+pub mod dungeon {
+    use bevy::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    use crate::GameState;
+
+    const FLOORS_PER_RANK: usize = 5;
+
+    #[derive(
+        Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize, Reflect,
+    )]
+    pub enum DungeonRank {
+        E,
+        D,
+        C,
+        B,
+        A,
+        S,
+    }
+
+    impl DungeonRank {
+        pub fn next(&self) -> DungeonRank {
+            match self {
+                DungeonRank::E => DungeonRank::D,
+                DungeonRank::D => DungeonRank::C,
+                DungeonRank::C => DungeonRank::B,
+                DungeonRank::B => DungeonRank::A,
+                DungeonRank::A | DungeonRank::S => DungeonRank::S,
+            }
+        }
+
+        pub fn xp_multiplier(&self) -> f32 {
+            match self {
+                DungeonRank::E => 1.0,
+                DungeonRank::D => 1.5,
+                DungeonRank::C => 2.0,
+                DungeonRank::B => 3.0,
+                DungeonRank::A => 4.0,
+                DungeonRank::S => 6.0,
+            }
+        }
+    }
+
+    #[derive(
+        Component, Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, Reflect,
+    )]
+    pub enum EnemyKind {
+        Worker,
+        Soldier,
+        Queen,
+    }
+
+    impl EnemyKind {
+        pub fn get_random_kind() -> EnemyKind {
+            use rand::random_range;
+
+            match random_range(0..10) {
+                0..=5 => EnemyKind::Worker,
+                6..=8 => EnemyKind::Soldier,
+                _ => EnemyKind::Queen,
+            }
+        }
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                EnemyKind::Worker => "Worker Bee",
+                EnemyKind::Soldier => "Soldier Bee",
+                EnemyKind::Queen => "Queen Bee",
+            }
+        }
+
+        pub fn max_health(&self) -> i32 {
+            match self {
+                EnemyKind::Worker => 70,
+                EnemyKind::Soldier => 100,
+                EnemyKind::Queen => 160,
+            }
+        }
+
+        pub fn base_xp(&self) -> u32 {
+            match self {
+                EnemyKind::Worker => 10,
+                EnemyKind::Soldier => 20,
+                EnemyKind::Queen => 50,
+            }
+        }
+
+        pub fn sprite_scale(&self) -> f32 {
+            match self {
+                EnemyKind::Worker => 0.8,
+                EnemyKind::Soldier => 1.0,
+                EnemyKind::Queen => 1.4,
+            }
+        }
+    }
+
+    /// What the player runs into after collecting their rewards.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum MapNode {
+        Battle,
+        Shop,
+        RestSite,
+        Event,
+    }
+
+    impl MapNode {
+        pub fn state(&self) -> GameState {
+            match self {
+                MapNode::Battle => GameState::Battle,
+                MapNode::Shop => GameState::Shop,
+                MapNode::RestSite => GameState::RestSite,
+                MapNode::Event => GameState::Event,
+            }
+        }
+    }
+
+    /// Where the player is in the current run and what they are fighting.
+    #[derive(Resource, Debug, Serialize, Reflect)]
+    pub struct Dungeon {
+        pub rank: DungeonRank,
+        pub floor: usize,
+        pub encounter: EnemyKind,
+    }
+
+    impl Default for Dungeon {
+        fn default() -> Self {
+            Dungeon {
+                rank: DungeonRank::E,
+                floor: 1,
+                encounter: EnemyKind::Worker,
+            }
+        }
+    }
+
+    impl Dungeon {
+        pub fn roll_next_node(&self) -> MapNode {
+            use rand::random_range;
+
+            match random_range(0..20) {
+                0..=3 => MapNode::Shop,
+                4..=6 => MapNode::RestSite,
+                7..=10 => MapNode::Event,
+                _ => MapNode::Battle,
+            }
+        }
+
+        pub fn advance(&mut self) {
+            self.floor += 1;
+            if self.floor % FLOORS_PER_RANK == 1 {
+                self.rank = self.rank.next();
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+pub mod floating_text {
+    use bevy::prelude::*;
+
+    use crate::card::card::CardEffect;
+    use crate::combat::combat::{CombatEvent, Combatant};
+    use crate::{EnemyEntity, PlayerEntity};
+
+    const FLOAT_SPEED: f32 = 40.0;
+    const FLOAT_LIFETIME: f32 = 1.0;
+    const FLOAT_Z_LAYER: f32 = 0.8;
+    /// Texts younger than this on the same target are still close enough to overlap.
+    const STACK_WINDOW: f32 = 0.5;
+    const STACK_OFFSET: f32 = 20.0;
+
+    const DAMAGE_COLOR: Color = Color::srgb(1.0, 0.3, 0.3);
+    const DOT_COLOR: Color = Color::srgb(0.7, 0.3, 1.0);
+    const HEAL_COLOR: Color = Color::srgb(0.3, 1.0, 0.4);
+    const SHIELD_COLOR: Color = Color::srgb(0.6, 0.85, 1.0);
+    const STATUS_COLOR: Color = Color::srgb(1.0, 1.0, 0.3);
+    const MISS_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
+    const DODGE_COLOR: Color = Color::srgb(0.5, 0.9, 1.0);
+    const CRIT_COLOR: Color = Color::srgb(1.0, 0.6, 0.0);
+
+    /// Text that drifts upwards over a combatant and fades out before despawning.
+    #[derive(Component)]
+    pub struct FloatingText {
+        target: Combatant,
+        lifetime: Timer,
+    }
+
+    fn spawn_floating_text(
+        commands: &mut Commands,
+        target: Combatant,
+        position: Vec3,
+        text: String,
+        color: Color,
+        font_size: f32,
+    ) {
+        commands.spawn((
+            Name::new("Floating Text"),
+            Text2d::new(text),
+            TextFont::from_font_size(font_size),
+            TextColor(color),
+            Transform::from_xyz(position.x, position.y, FLOAT_Z_LAYER),
+            FloatingText {
+                target,
+                lifetime: Timer::from_seconds(FLOAT_LIFETIME, TimerMode::Once),
+            },
+        ));
+    }
+
+    pub fn animate_floating_text(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut text_query: Query<(Entity, &mut FloatingText, &mut Transform, &mut TextColor)>,
+    ) {
+        for (entity, mut floating, mut transform, mut color) in text_query.iter_mut() {
+            floating.lifetime.tick(time.delta());
+            if floating.lifetime.finished() {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+            transform.translation.y += FLOAT_SPEED * time.delta_secs();
+            color.0.set_alpha(1.0 - floating.lifetime.fraction());
+        }
+    }
+
+    fn status_text(effect: &CardEffect) -> String {
+        match effect {
+            CardEffect::DamageOverTime { .. } => "Poisoned".to_string(),
+            CardEffect::Stun { .. } => "Stunned".to_string(),
+            _ => effect.name().to_string(),
+        }
+    }
+
+    /// Pops up numbers and labels over whoever was hit, healed or afflicted. Texts that
+    /// would land on top of each other are stacked upwards instead.
+    pub fn show_combat_text(
+        mut commands: Commands,
+        mut combat_events: EventReader<CombatEvent>,
+        text_query: Query<&FloatingText>,
+        player_query: Query<&GlobalTransform, With<PlayerEntity>>,
+        enemy_query: Query<&GlobalTransform, With<EnemyEntity>>,
+    ) {
+        let recent = |target: Combatant| {
+            text_query
+                .iter()
+                .filter(|text| text.target == target && text.lifetime.elapsed_secs() < STACK_WINDOW)
+                .count()
+        };
+        let mut stacked = [recent(Combatant::Player), recent(Combatant::Enemy)];
+
+        for event in combat_events.read() {
+            let (target, text, color, font_size) = match event {
+                CombatEvent::DamageDealt {
+                    target,
+                    amount,
+                    over_time,
+                } => (
+                    *target,
+                    format!("-{}", amount),
+                    if *over_time { DOT_COLOR } else { DAMAGE_COLOR },
+                    if *over_time { 16.0 } else { 22.0 },
+                ),
+                CombatEvent::Healed { target, amount } => {
+                    (*target, format!("+{}", amount), HEAL_COLOR, 22.0)
+                }
+                CombatEvent::Shielded { target, amount } => {
+                    (*target, format!("+{} shield", amount), SHIELD_COLOR, 18.0)
+                }
+                CombatEvent::Absorbed { target, amount } => {
+                    (*target, format!("Blocked {}", amount), SHIELD_COLOR, 16.0)
+                }
+                CombatEvent::StatusApplied { target, effect } => {
+                    (*target, status_text(effect), STATUS_COLOR, 16.0)
+                }
+                CombatEvent::Missed {
+                    target,
+                    dodged: false,
+                } => (*target, "MISS".to_string(), MISS_COLOR, 18.0),
+                CombatEvent::Missed {
+                    target,
+                    dodged: true,
+                } => (*target, "DODGE".to_string(), DODGE_COLOR, 18.0),
+                CombatEvent::CriticalHit { target } => {
+                    (*target, "CRIT".to_string(), CRIT_COLOR, 20.0)
+                }
+                _ => continue,
+            };
+            let position = match target {
+                Combatant::Player => player_query.get_single(),
+                Combatant::Enemy => enemy_query.get_single(),
+            };
+            let Ok(position) = position else {
+                continue;
+            };
+
+            let stack = &mut stacked[target as usize];
+            let offset = Vec3::new(0.0, *stack as f32 * STACK_OFFSET, 0.0);
+            *stack += 1;
+            spawn_floating_text(
+                &mut commands,
+                target,
+                position.translation() + offset,
+                text,
+                color,
+                font_size,
+            );
+        }
+    }
+}
@@ -0,0 +1,283 @@
+pub mod focus {
+    use bevy::{
+        ecs::system::SystemParam,
+        picking::{
+            backend::HitData,
+            pointer::{Location, PointerId},
+        },
+        prelude::*,
+        render::camera::NormalizedRenderTarget,
+        window::{PrimaryWindow, WindowRef},
+    };
+
+    use crate::input::input::{Action, ActionInput};
+
+    const CURSOR_COLOR: Color = Color::srgba(1.0, 0.9, 0.3, 0.35);
+    const CURSOR_PADDING: f32 = 8.0;
+    const CURSOR_Z_LAYER: f32 = 1.5;
+    const OUTLINE_WIDTH: f32 = 3.0;
+    /// Sideways distance counts for more than distance along the direction, so moving
+    /// favours what's straight ahead.
+    const SIDEWAYS_WEIGHT: f32 = 2.0;
+
+    /// Something the keyboard or a gamepad can move to and confirm, the same as clicking it.
+    #[derive(Component, Default)]
+    pub struct Focusable;
+
+    #[derive(Resource, Default)]
+    pub struct Focus(pub Option<Entity>);
+
+    #[derive(Component)]
+    pub struct FocusCursor;
+
+    /// Which screen a focusable belongs to. Only the topmost one can take focus, so a menu
+    /// can't focus the battle behind it. UI is drawn over every sprite, so any UI layer
+    /// outranks the world, and UI layers go by their `GlobalZIndex`.
+    #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    enum FocusLayer {
+        /// Cards, the enemy and the potion belt, however they're stacked.
+        World,
+        Ui(i32),
+    }
+
+    pub fn spawn_focus_cursor(mut commands: Commands) {
+        commands.spawn((
+            Name::new("Focus Cursor"),
+            Sprite {
+                color: CURSOR_COLOR,
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, CURSOR_Z_LAYER),
+            Visibility::Hidden,
+            PickingBehavior::IGNORE,
+            FocusCursor,
+        ));
+    }
+
+    /// Every focusable, whether it's a sprite in the world or a UI node.
+    #[derive(SystemParam)]
+    pub struct Focusables<'w, 's> {
+        focusable_query: Query<
+            'w,
+            's,
+            (
+                Entity,
+                &'static GlobalTransform,
+                &'static InheritedVisibility,
+                Has<Node>,
+            ),
+            With<Focusable>,
+        >,
+        layer_query: Query<'w, 's, (Option<&'static Parent>, Option<&'static GlobalZIndex>)>,
+    }
+
+    impl Focusables<'_, '_> {
+        /// A UI node sits on the layer of the closest ancestor with a `GlobalZIndex`.
+        fn ui_layer(&self, mut entity: Entity) -> i32 {
+            loop {
+                let Ok((parent, z_index)) = self.layer_query.get(entity) else {
+                    return 0;
+                };
+                if let Some(z_index) = z_index {
+                    return z_index.0;
+                }
+                let Some(parent) = parent else {
+                    return 0;
+                };
+                entity = parent.get();
+            }
+        }
+
+        /// Visible focusables on the topmost layer. Positions have y pointing up.
+        fn candidates(&self) -> Vec<(Entity, Vec2)> {
+            let visible: Vec<(Entity, Vec2, FocusLayer)> = self
+                .focusable_query
+                .iter()
+                .filter(|(_, _, visibility, _)| visibility.get())
+                .map(|(entity, transform, _, is_ui)| {
+                    let position = transform.translation().truncate();
+                    if is_ui {
+                        // UI positions are measured down from the top of the window
+                        let layer = FocusLayer::Ui(self.ui_layer(entity));
+                        (entity, Vec2::new(position.x, -position.y), layer)
+                    } else {
+                        (entity, position, FocusLayer::World)
+                    }
+                })
+                .collect();
+            let Some(top) = visible.iter().map(|(_, _, layer)| *layer).max() else {
+                return vec![];
+            };
+            visible
+                .into_iter()
+                .filter(|(_, _, layer)| *layer == top)
+                .map(|(entity, position, _)| (entity, position))
+                .collect()
+        }
+    }
+
+    pub fn navigate_focus(
+        actions: Res<ActionInput>,
+        mut focus: ResMut<Focus>,
+        focusables: Focusables,
+    ) {
+        let direction = [
+            (Action::NavigateUp, Vec2::Y),
+            (Action::NavigateDown, Vec2::NEG_Y),
+            (Action::NavigateLeft, Vec2::NEG_X),
+            (Action::NavigateRight, Vec2::X),
+        ]
+        .into_iter()
+        .find(|(action, _)| actions.just_pressed(*action))
+        .map(|(_, direction)| direction);
+        if actions.just_pressed(Action::Cancel) {
+            focus.0 = None;
+        }
+        let Some(direction) = direction else {
+            return;
+        };
+
+        let candidates = focusables.candidates();
+        let current = focus.0.and_then(|focused| {
+            candidates
+                .iter()
+                .find(|(entity, _)| *entity == focused)
+                .map(|(_, position)| *position)
+        });
+        let Some(current) = current else {
+            // Nothing focused yet, so start from the top left
+            focus.0 = candidates
+                .iter()
+                .min_by(|(_, a), (_, b)| b.y.total_cmp(&a.y).then(a.x.total_cmp(&b.x)))
+                .map(|(entity, _)| *entity);
+            return;
+        };
+
+        let score = |position: Vec2| {
+            let offset = position - current;
+            let ahead = offset.dot(direction);
+            let sideways = (offset - direction * ahead).length();
+            (ahead > 0.0).then_some(ahead + sideways * SIDEWAYS_WEIGHT)
+        };
+        if let Some((next, _)) = candidates
+            .iter()
+            .filter_map(|(entity, position)| score(*position).map(|score| (*entity, score)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        {
+            focus.0 = Some(next);
+        }
+    }
+
+    /// Confirm clicks whatever is focused, so every button works without a mouse.
+    pub fn confirm_focus(
+        mut commands: Commands,
+        actions: Res<ActionInput>,
+        focus: Res<Focus>,
+        focusable_query: Query<(&GlobalTransform, Has<Node>), With<Focusable>>,
+        camera_query: Query<(Entity, &Camera, &GlobalTransform)>,
+        window_query: Query<(Entity, &Window), With<PrimaryWindow>>,
+    ) {
+        if !actions.just_pressed(Action::Confirm) {
+            return;
+        }
+        let Some(focused) = focus.0 else {
+            return;
+        };
+        let Ok((transform, is_ui)) = focusable_query.get(focused) else {
+            return;
+        };
+        let (Ok((camera_entity, camera, camera_transform)), Ok((window_entity, window))) =
+            (camera_query.get_single(), window_query.get_single())
+        else {
+            return;
+        };
+        let Some(target) = WindowRef::Primary.normalize(Some(window_entity)) else {
+            return;
+        };
+
+        let world_position = transform.translation();
+        // UI nodes are already placed in physical window pixels
+        let position = if is_ui {
+            world_position.truncate() / window.scale_factor()
+        } else {
+            camera
+                .world_to_viewport(camera_transform, world_position)
+                .unwrap_or_default()
+        };
+        let location = Location {
+            target: NormalizedRenderTarget::Window(target),
+            position,
+        };
+        let click = Click {
+            button: PointerButton::Primary,
+            hit: HitData::new(camera_entity, 0.0, Some(world_position), None),
+            duration: default(),
+        };
+        commands.trigger_targets(
+            Pointer::new(focused, PointerId::Mouse, location, click),
+            focused,
+        );
+    }
+
+    /// Draws a frame around the focused entity and lets go of it once it's gone. UI nodes
+    /// are drawn over the frame, so they get an outline instead.
+    pub fn update_focus_cursor(
+        mut commands: Commands,
+        mut focus: ResMut<Focus>,
+        mut outlined: Local<Option<Entity>>,
+        focusable_query: Query<
+            (
+                &GlobalTransform,
+                &InheritedVisibility,
+                Option<&Sprite>,
+                Has<Node>,
+            ),
+            (With<Focusable>, Without<FocusCursor>),
+        >,
+        mut cursor_query: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<FocusCursor>>,
+    ) {
+        let Ok((mut cursor_transform, mut cursor_sprite, mut cursor_visibility)) =
+            cursor_query.get_single_mut()
+        else {
+            return;
+        };
+        let focused = focus
+            .0
+            .and_then(|focused| focusable_query.get(focused).ok())
+            .filter(|(_, visibility, _, _)| visibility.get());
+        let outline_target = focused.filter(|(_, _, _, is_ui)| *is_ui).and(focus.0);
+        if *outlined != outline_target {
+            if let Some(previous) = outlined.take() {
+                commands.entity(previous).remove::<Outline>();
+            }
+            if let Some(target) = outline_target {
+                commands.entity(target).insert(Outline::new(
+                    Val::Px(OUTLINE_WIDTH),
+                    Val::ZERO,
+                    CURSOR_COLOR.with_alpha(1.0),
+                ));
+            }
+            *outlined = outline_target;
+        }
+
+        let Some((transform, _, sprite, is_ui)) = focused else {
+            if focus.0.is_some() {
+                focus.0 = None;
+            }
+            *cursor_visibility = Visibility::Hidden;
+            return;
+        };
+        if is_ui {
+            *cursor_visibility = Visibility::Hidden;
+            return;
+        }
+
+        let size = sprite
+            .and_then(|sprite| sprite.custom_size)
+            .unwrap_or(Vec2::splat(32.0));
+        let position = transform.translation();
+        cursor_transform.translation = Vec3::new(position.x, position.y, CURSOR_Z_LAYER);
+        cursor_sprite.custom_size = Some(size + Vec2::splat(CURSOR_PADDING));
+        *cursor_visibility = Visibility::Visible;
+    }
+}
@@ -0,0 +1,138 @@
+pub mod health_bar {
+    use bevy::prelude::*;
+
+    use crate::combat::combat::Shield;
+    use crate::{EnemyHealth, MaxHealth, PlayerHealth, GREEN, RED, YELLOW};
+
+    const BAR_WIDTH: f32 = 70.0;
+    const BAR_HEIGHT: f32 = 7.0;
+    /// How long the damage trail waits before it starts catching up.
+    const TRAIL_DELAY: f32 = 0.4;
+    /// Fraction of the bar the trail drains per second.
+    const TRAIL_SPEED: f32 = 0.6;
+    const TRAIL_COLOR: Color = Color::srgb(1.0, 0.9, 0.6);
+    const SHIELD_COLOR: Color = Color::srgba(0.6, 0.85, 1.0, 0.75);
+
+    #[derive(Component)]
+    pub struct HealthBar;
+
+    /// The lighter strip behind the bar that shows recent damage before draining away.
+    #[derive(Component)]
+    pub struct HealthTrail {
+        fraction: f32,
+        delay: Timer,
+    }
+
+    #[derive(Component)]
+    pub struct ShieldOverlay;
+
+    fn spawn_strip(parent: &mut ChildBuilder, y: f32, z: f32, color: Color, marker: impl Bundle) {
+        parent.spawn((
+            Sprite {
+                color,
+                custom_size: Some(Vec2::new(BAR_WIDTH, BAR_HEIGHT)),
+                ..default()
+            },
+            Transform::from_xyz(0.0, y, z),
+            marker,
+        ));
+    }
+
+    pub fn add_health_bar(parent: &mut ChildBuilder, y: f32) {
+        spawn_strip(parent, y, 0.1, Color::srgb(0.2, 0.2, 0.2), ());
+        spawn_strip(
+            parent,
+            y,
+            0.15,
+            TRAIL_COLOR,
+            HealthTrail {
+                fraction: 1.0,
+                delay: Timer::from_seconds(TRAIL_DELAY, TimerMode::Once),
+            },
+        );
+        spawn_strip(parent, y, 0.2, GREEN, HealthBar);
+        spawn_strip(parent, y, 0.25, SHIELD_COLOR, ShieldOverlay);
+    }
+
+    /// Sizes a strip to `fraction` of the bar, anchored to its left edge.
+    fn set_fill(transform: &mut Transform, sprite: &mut Sprite, fraction: f32) {
+        let width = BAR_WIDTH * fraction.clamp(0.0, 1.0);
+        sprite.custom_size = Some(Vec2::new(width, BAR_HEIGHT));
+        transform.translation.x = -BAR_WIDTH / 2.0 + width / 2.0;
+    }
+
+    pub fn update_health_bars(
+        time: Res<Time>,
+        owner_query: Query<(
+            Option<&PlayerHealth>,
+            Option<&EnemyHealth>,
+            &MaxHealth,
+            Option<&Shield>,
+            &Children,
+        )>,
+        mut bar_query: Query<
+            (&mut Transform, &mut Sprite),
+            (
+                With<HealthBar>,
+                Without<HealthTrail>,
+                Without<ShieldOverlay>,
+            ),
+        >,
+        mut trail_query: Query<
+            (&mut Transform, &mut Sprite, &mut HealthTrail),
+            (Without<HealthBar>, Without<ShieldOverlay>),
+        >,
+        mut shield_query: Query<
+            (&mut Transform, &mut Sprite),
+            (
+                With<ShieldOverlay>,
+                Without<HealthBar>,
+                Without<HealthTrail>,
+            ),
+        >,
+    ) {
+        for (player_health, enemy_health, max_health, shield, children) in owner_query.iter() {
+            let health = match (player_health, enemy_health) {
+                (Some(health), _) => health.0,
+                (_, Some(health)) => health.0,
+                _ => continue,
+            };
+            let max = max_health.0.max(1) as f32;
+            let fraction = health as f32 / max;
+
+            for child in children.iter() {
+                if let Ok((mut transform, mut sprite)) = bar_query.get_mut(*child) {
+                    set_fill(&mut transform, &mut sprite, fraction);
+                    sprite.color = if fraction < 0.3 {
+                        RED
+                    } else if fraction < 0.6 {
+                        YELLOW
+                    } else {
+                        GREEN
+                    };
+                }
+                if let Ok((mut transform, mut sprite, mut trail)) = trail_query.get_mut(*child) {
+                    if fraction >= trail.fraction {
+                        // Heals snap the trail up with the bar
+                        trail.fraction = fraction;
+                        trail.delay.reset();
+                    } else {
+                        trail.delay.tick(time.delta());
+                        if trail.delay.finished() {
+                            trail.fraction =
+                                (trail.fraction - TRAIL_SPEED * time.delta_secs()).max(fraction);
+                        }
+                        if trail.fraction == fraction {
+                            trail.delay.reset();
+                        }
+                    }
+                    set_fill(&mut transform, &mut sprite, trail.fraction);
+                }
+                if let Ok((mut transform, mut sprite)) = shield_query.get_mut(*child) {
+                    let shield = shield.map(|shield| shield.0).unwrap_or(0);
+                    set_fill(&mut transform, &mut sprite, shield as f32 / max);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,298 @@
+pub mod input {
+    use bevy::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    const BINDINGS_PATH: &str = "bindings.json";
+    /// How far a stick has to be pushed before it counts as a navigation press.
+    const STICK_THRESHOLD: f32 = 0.6;
+    /// Cards in hand that get their own play hotkey.
+    pub const CARD_HOTKEYS: usize = 9;
+    const CARD_KEYS: [KeyCode; CARD_HOTKEYS] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+        KeyCode::Digit6,
+        KeyCode::Digit7,
+        KeyCode::Digit8,
+        KeyCode::Digit9,
+    ];
+
+    /// Everything the player can do from a keyboard or gamepad, independent of which key or
+    /// button is bound to it.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum Action {
+        OpenInventory,
+        Pause,
+        Quit,
+        NavigateUp,
+        NavigateDown,
+        NavigateLeft,
+        NavigateRight,
+        Confirm,
+        Cancel,
+        /// Plays the nth card in hand, counting from the left.
+        PlayCard(usize),
+        UsePotion(usize),
+        TogglePlayMode,
+        ToggleAiming,
+        ToggleCombatLog,
+        ScrollLogUp,
+        ScrollLogDown,
+        SaveTranscript,
+    }
+
+    impl Action {
+        pub fn name(&self) -> String {
+            match self {
+                Action::OpenInventory => "Inventory".to_string(),
+                Action::Pause => "Pause".to_string(),
+                Action::Quit => "Quit".to_string(),
+                Action::NavigateUp => "Up".to_string(),
+                Action::NavigateDown => "Down".to_string(),
+                Action::NavigateLeft => "Left".to_string(),
+                Action::NavigateRight => "Right".to_string(),
+                Action::Confirm => "Confirm".to_string(),
+                Action::Cancel => "Cancel".to_string(),
+                Action::PlayCard(index) => format!("Play card {}", index + 1),
+                Action::UsePotion(slot) => format!("Potion {}", slot + 1),
+                Action::TogglePlayMode => "Play mode".to_string(),
+                Action::ToggleAiming => "Aiming".to_string(),
+                Action::ToggleCombatLog => "Combat log".to_string(),
+                Action::ScrollLogUp => "Log up".to_string(),
+                Action::ScrollLogDown => "Log down".to_string(),
+                Action::SaveTranscript => "Save log".to_string(),
+            }
+        }
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct Binding {
+        pub action: Action,
+        pub keys: Vec<KeyCode>,
+        pub buttons: Vec<GamepadButton>,
+    }
+
+    fn bind(action: Action, keys: &[KeyCode], buttons: &[GamepadButton]) -> Binding {
+        Binding {
+            action,
+            keys: keys.to_vec(),
+            buttons: buttons.to_vec(),
+        }
+    }
+
+    /// Which keys and gamepad buttons trigger each action, kept in `bindings.json`.
+    #[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+    pub struct InputBindings {
+        pub bindings: Vec<Binding>,
+    }
+
+    impl Default for InputBindings {
+        fn default() -> Self {
+            use GamepadButton as Pad;
+            let mut bindings = vec![
+                bind(Action::OpenInventory, &[KeyCode::KeyI], &[Pad::North]),
+                bind(Action::Pause, &[KeyCode::Escape], &[Pad::Start]),
+                bind(Action::Quit, &[KeyCode::KeyQ], &[]),
+                bind(Action::NavigateUp, &[KeyCode::ArrowUp], &[Pad::DPadUp]),
+                bind(
+                    Action::NavigateDown,
+                    &[KeyCode::ArrowDown],
+                    &[Pad::DPadDown],
+                ),
+                bind(
+                    Action::NavigateLeft,
+                    &[KeyCode::ArrowLeft],
+                    &[Pad::DPadLeft],
+                ),
+                bind(
+                    Action::NavigateRight,
+                    &[KeyCode::ArrowRight],
+                    &[Pad::DPadRight],
+                ),
+                bind(
+                    Action::Confirm,
+                    &[KeyCode::Enter, KeyCode::Space],
+                    &[Pad::South],
+                ),
+                bind(Action::Cancel, &[KeyCode::Backspace], &[Pad::East]),
+            ];
+            for (index, key) in CARD_KEYS.into_iter().enumerate() {
+                bindings.push(bind(Action::PlayCard(index), &[key], &[]));
+            }
+            bindings.extend([
+                bind(Action::UsePotion(0), &[KeyCode::KeyZ], &[Pad::West]),
+                bind(Action::UsePotion(1), &[KeyCode::KeyX], &[Pad::LeftTrigger]),
+                bind(Action::UsePotion(2), &[KeyCode::KeyC], &[Pad::RightTrigger]),
+                bind(Action::TogglePlayMode, &[KeyCode::KeyM], &[Pad::Select]),
+                bind(Action::ToggleAiming, &[KeyCode::KeyT], &[]),
+                bind(Action::ToggleCombatLog, &[KeyCode::KeyL], &[]),
+                bind(
+                    Action::ScrollLogUp,
+                    &[KeyCode::PageUp],
+                    &[Pad::LeftTrigger2],
+                ),
+                bind(
+                    Action::ScrollLogDown,
+                    &[KeyCode::PageDown],
+                    &[Pad::RightTrigger2],
+                ),
+                bind(Action::SaveTranscript, &[KeyCode::F5], &[]),
+            ]);
+            InputBindings { bindings }
+        }
+    }
+
+    impl InputBindings {
+        /// Starts from the defaults and takes whatever the file overrides, so actions added
+        /// since the file was written still get a binding.
+        pub fn load() -> InputBindings {
+            let mut bindings = InputBindings::default();
+            let Ok(contents) = std::fs::read_to_string(BINDINGS_PATH) else {
+                return bindings;
+            };
+            match serde_json::from_str::<InputBindings>(&contents) {
+                Ok(saved) => {
+                    for saved in saved.bindings {
+                        if let Some(binding) = bindings.get_mut(saved.action) {
+                            *binding = saved;
+                        }
+                    }
+                }
+                Err(err) => println!("[input] Ignoring broken {}: {}", BINDINGS_PATH, err),
+            }
+            bindings
+        }
+
+        pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+            std::fs::write(BINDINGS_PATH, serde_json::to_string_pretty(self)?)?;
+            Ok(())
+        }
+
+        pub fn get(&self, action: Action) -> Option<&Binding> {
+            self.bindings
+                .iter()
+                .find(|binding| binding.action == action)
+        }
+
+        fn get_mut(&mut self, action: Action) -> Option<&mut Binding> {
+            self.bindings
+                .iter_mut()
+                .find(|binding| binding.action == action)
+        }
+
+        /// Makes `key` the only key for `action`, taking it away from any other action.
+        pub fn rebind_key(&mut self, action: Action, key: KeyCode) {
+            for binding in self.bindings.iter_mut() {
+                binding.keys.retain(|bound| *bound != key);
+            }
+            if let Some(binding) = self.get_mut(action) {
+                binding.keys = vec![key];
+            }
+        }
+
+        /// Makes `button` the only gamepad button for `action`, taking it away from any other
+        /// action.
+        pub fn rebind_button(&mut self, action: Action, button: GamepadButton) {
+            for binding in self.bindings.iter_mut() {
+                binding.buttons.retain(|bound| *bound != button);
+            }
+            if let Some(binding) = self.get_mut(action) {
+                binding.buttons = vec![button];
+            }
+        }
+
+        /// Short label for the first key bound to an action, for on-screen hints.
+        pub fn key_label(&self, action: Action) -> String {
+            self.get(action)
+                .and_then(|binding| binding.keys.first())
+                .map(|key| key_name(*key))
+                .unwrap_or_default()
+        }
+    }
+
+    pub fn key_name(key: KeyCode) -> String {
+        let name = format!("{:?}", key);
+        name.strip_prefix("Key")
+            .or_else(|| name.strip_prefix("Digit"))
+            .unwrap_or(&name)
+            .to_string()
+    }
+
+    /// The action the controls screen is waiting to rebind; no actions fire meanwhile, so
+    /// the key being bound doesn't also do something.
+    #[derive(Resource, Default)]
+    pub struct Rebinding(pub Option<Action>);
+
+    /// The actions triggered this frame, read from the keyboard and every connected gamepad.
+    #[derive(Resource, Default)]
+    pub struct ActionInput {
+        just_pressed: Vec<Action>,
+        /// Direction the sticks were pushed last frame, so holding one only navigates once.
+        stick: IVec2,
+    }
+
+    impl ActionInput {
+        pub fn just_pressed(&self, action: Action) -> bool {
+            self.just_pressed.contains(&action)
+        }
+    }
+
+    fn stick_direction(stick: Vec2) -> IVec2 {
+        let axis = |value: f32| {
+            if value > STICK_THRESHOLD {
+                1
+            } else if value < -STICK_THRESHOLD {
+                -1
+            } else {
+                0
+            }
+        };
+        IVec2::new(axis(stick.x), axis(stick.y))
+    }
+
+    pub fn read_actions(
+        keys: Res<ButtonInput<KeyCode>>,
+        gamepads: Query<&Gamepad>,
+        bindings: Res<InputBindings>,
+        rebinding: Res<Rebinding>,
+        mut input: ResMut<ActionInput>,
+    ) {
+        input.just_pressed.clear();
+        if rebinding.0.is_some() {
+            return;
+        }
+
+        for binding in bindings.bindings.iter() {
+            let pressed = binding.keys.iter().any(|key| keys.just_pressed(*key))
+                || gamepads.iter().any(|gamepad| {
+                    binding
+                        .buttons
+                        .iter()
+                        .any(|button| gamepad.just_pressed(*button))
+                });
+            if pressed {
+                input.just_pressed.push(binding.action);
+            }
+        }
+
+        // The left stick always navigates, on top of whatever is bound
+        let stick = gamepads
+            .iter()
+            .map(|gamepad| stick_direction(gamepad.left_stick()))
+            .find(|direction| *direction != IVec2::ZERO)
+            .unwrap_or(IVec2::ZERO);
+        let previous = input.stick;
+        input.stick = stick;
+        let mut push_once = |pushed: bool, was_pushed: bool, action: Action| {
+            if pushed && !was_pushed && !input.just_pressed.contains(&action) {
+                input.just_pressed.push(action);
+            }
+        };
+        push_once(stick.y > 0, previous.y > 0, Action::NavigateUp);
+        push_once(stick.y < 0, previous.y < 0, Action::NavigateDown);
+        push_once(stick.x < 0, previous.x < 0, Action::NavigateLeft);
+        push_once(stick.x > 0, previous.x > 0, Action::NavigateRight);
+    }
+}
@@ -0,0 +1,474 @@
+pub mod inventory_screen {
+    use std::fmt::Debug;
+
+    use bevy::{input::mouse::MouseWheel, prelude::*};
+
+    use crate::focus::focus::Focusable;
+    use crate::input::input::{Action, ActionInput};
+    use crate::layout::layout::{box_node, label_node};
+    use crate::{
+        GameMenu, Inventory, InventoryDisplay, ItemCategory, LootItem, INVENTORY_CAPACITY,
+    };
+
+    const PANEL_SIZE: Vec2 = Vec2::new(600.0, 420.0);
+    const LIST_ROW_HEIGHT: f32 = 40.0;
+    const LIST_VISIBLE_ROWS: usize = 8;
+    const GRID_COLUMNS: usize = 4;
+    const GRID_CELL_SIZE: Vec2 = Vec2::new(84.0, 70.0);
+    const GRID_VISIBLE_ROWS: usize = 4;
+    const DETAIL_WIDTH: f32 = 180.0;
+
+    #[derive(Clone, Copy, PartialEq, Default)]
+    pub enum InventoryLayout {
+        #[default]
+        List,
+        Grid,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Default)]
+    pub enum InventorySort {
+        #[default]
+        Rarity,
+        Name,
+        Type,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Default)]
+    pub enum InventoryFilter {
+        #[default]
+        All,
+        Only(ItemCategory),
+    }
+
+    impl InventoryLayout {
+        fn next(&self) -> Self {
+            match self {
+                InventoryLayout::List => InventoryLayout::Grid,
+                InventoryLayout::Grid => InventoryLayout::List,
+            }
+        }
+
+        fn label(&self) -> &'static str {
+            match self {
+                InventoryLayout::List => "View: List",
+                InventoryLayout::Grid => "View: Grid",
+            }
+        }
+
+        /// How many stacks fit on one row, and how many rows fit on screen.
+        fn shape(&self) -> (usize, usize) {
+            match self {
+                InventoryLayout::List => (1, LIST_VISIBLE_ROWS),
+                InventoryLayout::Grid => (GRID_COLUMNS, GRID_VISIBLE_ROWS),
+            }
+        }
+    }
+
+    impl InventorySort {
+        fn next(&self) -> Self {
+            match self {
+                InventorySort::Rarity => InventorySort::Name,
+                InventorySort::Name => InventorySort::Type,
+                InventorySort::Type => InventorySort::Rarity,
+            }
+        }
+
+        fn label(&self) -> &'static str {
+            match self {
+                InventorySort::Rarity => "Sort: Rarity",
+                InventorySort::Name => "Sort: Name",
+                InventorySort::Type => "Sort: Type",
+            }
+        }
+    }
+
+    impl InventoryFilter {
+        fn next(&self) -> Self {
+            match self {
+                InventoryFilter::All => InventoryFilter::Only(ItemCategory::Consumable),
+                InventoryFilter::Only(ItemCategory::Consumable) => {
+                    InventoryFilter::Only(ItemCategory::Weapon)
+                }
+                InventoryFilter::Only(ItemCategory::Weapon) => {
+                    InventoryFilter::Only(ItemCategory::Misc)
+                }
+                InventoryFilter::Only(ItemCategory::Misc) => InventoryFilter::All,
+            }
+        }
+
+        fn label(&self) -> String {
+            match self {
+                InventoryFilter::All => "Filter: All".to_string(),
+                InventoryFilter::Only(category) => format!("Filter: {}", category.name()),
+            }
+        }
+
+        fn allows(&self, item: &LootItem) -> bool {
+            match self {
+                InventoryFilter::All => true,
+                InventoryFilter::Only(category) => item.category() == *category,
+            }
+        }
+    }
+
+    /// How the inventory screen is laid out. Kept between openings of the screen.
+    #[derive(Resource, Default)]
+    pub struct InventoryView {
+        layout: InventoryLayout,
+        sort: InventorySort,
+        filter: InventoryFilter,
+        /// First visible row
+        scroll: usize,
+        selected: Option<(String, crate::LootRarity)>,
+    }
+
+    /// Identical items shown as a single entry.
+    struct ItemStack {
+        item: LootItem,
+        count: usize,
+    }
+
+    fn visible_stacks(inventory: &Inventory, view: &InventoryView) -> Vec<ItemStack> {
+        let mut stacks: Vec<ItemStack> = vec![];
+        for item in inventory
+            .items
+            .iter()
+            .filter(|item| view.filter.allows(item))
+        {
+            match stacks
+                .iter_mut()
+                .find(|stack| stack.item.name == item.name && stack.item.rarity == item.rarity)
+            {
+                Some(stack) => stack.count += 1,
+                None => stacks.push(ItemStack {
+                    item: item.clone(),
+                    count: 1,
+                }),
+            }
+        }
+
+        match view.sort {
+            InventorySort::Rarity => stacks.sort_by(|a, b| {
+                b.item
+                    .rarity
+                    .cmp(&a.item.rarity)
+                    .then_with(|| a.item.name.cmp(&b.item.name))
+            }),
+            InventorySort::Name => stacks.sort_by(|a, b| {
+                a.item
+                    .name
+                    .cmp(&b.item.name)
+                    .then_with(|| b.item.rarity.cmp(&a.item.rarity))
+            }),
+            InventorySort::Type => stacks.sort_by(|a, b| {
+                a.item
+                    .category()
+                    .cmp(&b.item.category())
+                    .then_with(|| a.item.name.cmp(&b.item.name))
+            }),
+        }
+        stacks
+    }
+
+    fn max_scroll(stack_count: usize, layout: InventoryLayout) -> usize {
+        let (columns, visible_rows) = layout.shape();
+        let rows = stack_count.div_ceil(columns);
+        rows.saturating_sub(visible_rows)
+    }
+
+    #[derive(Component)]
+    pub struct InventoryContent;
+
+    #[derive(Component)]
+    pub struct InventoryDetail;
+
+    #[derive(Component)]
+    pub struct InventoryTitle;
+
+    #[derive(Component, Clone, Copy)]
+    pub enum ToolbarButton {
+        Layout,
+        Sort,
+        Filter,
+    }
+
+    pub fn spawn_inventory_screen(mut commands: Commands) {
+        commands
+            .spawn((
+                Name::new("Inventory Screen"),
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                // Only the panel itself catches clicks
+                PickingBehavior::IGNORE,
+                InventoryDisplay,
+                GameMenu::Inventory,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Px(PANEL_SIZE.x),
+                            height: Val::Px(PANEL_SIZE.y),
+                            max_width: Val::Percent(100.0),
+                            max_height: Val::Percent(100.0),
+                            padding: UiRect::all(Val::Px(10.0)),
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(10.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+                    ))
+                    .with_children(|parent| {
+                        spawn_toolbar(parent);
+                        parent
+                            .spawn(Node {
+                                flex_grow: 1.0,
+                                column_gap: Val::Px(10.0),
+                                min_height: Val::Px(0.0),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Name::new("Inventory Content"),
+                                    Node {
+                                        flex_grow: 1.0,
+                                        flex_direction: FlexDirection::Column,
+                                        align_items: AlignItems::Center,
+                                        row_gap: Val::Px(6.0),
+                                        ..default()
+                                    },
+                                    InventoryContent,
+                                ));
+                                parent.spawn((
+                                    Name::new("Inventory Detail"),
+                                    Node {
+                                        width: Val::Px(DETAIL_WIDTH),
+                                        padding: UiRect::all(Val::Px(8.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgb(0.12, 0.12, 0.12)),
+                                    InventoryDetail,
+                                ));
+                            });
+                    });
+            });
+    }
+
+    fn spawn_toolbar(parent: &mut ChildBuilder) {
+        parent
+            .spawn(Node {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(10.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new("Inventory"),
+                    TextColor(Color::WHITE),
+                    Node {
+                        flex_grow: 1.0,
+                        ..default()
+                    },
+                    InventoryTitle,
+                ));
+
+                for button in [
+                    ToolbarButton::Layout,
+                    ToolbarButton::Sort,
+                    ToolbarButton::Filter,
+                ] {
+                    parent
+                        .spawn((
+                            box_node(Vec2::new(120.0, 28.0), Color::srgb(0.3, 0.3, 0.3)),
+                            button,
+                            // Up and down scroll the list, so only the toolbar takes focus
+                            Focusable,
+                        ))
+                        .with_child(label_node("", 13.0, Color::WHITE))
+                        .observe(toolbar_on::<Pointer<Click>>(button));
+                }
+            });
+    }
+
+    /// Rebuilds the visible part of the inventory whenever the items or the view change.
+    pub fn refresh_inventory_screen(
+        mut commands: Commands,
+        inventory: Res<Inventory>,
+        mut view: ResMut<InventoryView>,
+        new_screen: Query<(), Added<InventoryContent>>,
+        content_query: Query<Entity, With<InventoryContent>>,
+        detail_query: Query<Entity, With<InventoryDetail>>,
+        mut title_query: Query<&mut Text, With<InventoryTitle>>,
+        toolbar_query: Query<(&ToolbarButton, &Children)>,
+        mut text_query: Query<&mut Text, Without<InventoryTitle>>,
+    ) {
+        if !inventory.is_changed() && !view.is_changed() && new_screen.is_empty() {
+            return;
+        }
+        let (Ok(content), Ok(detail)) = (content_query.get_single(), detail_query.get_single())
+        else {
+            return;
+        };
+
+        let stacks = visible_stacks(&inventory, &view);
+        let max_scroll = max_scroll(stacks.len(), view.layout);
+        if view.scroll > max_scroll {
+            view.scroll = max_scroll;
+        }
+
+        for mut title in title_query.iter_mut() {
+            title.0 = format!("Inventory {}/{}", inventory.items.len(), INVENTORY_CAPACITY);
+        }
+        for (button, children) in toolbar_query.iter() {
+            let label = match button {
+                ToolbarButton::Layout => view.layout.label().to_string(),
+                ToolbarButton::Sort => view.sort.label().to_string(),
+                ToolbarButton::Filter => view.filter.label(),
+            };
+            for child in children.iter() {
+                if let Ok(mut text) = text_query.get_mut(*child) {
+                    text.0 = label.clone();
+                }
+            }
+        }
+
+        let (columns, visible_rows) = view.layout.shape();
+        commands.entity(content).despawn_descendants();
+        commands.entity(content).with_children(|parent| {
+            if stacks.is_empty() {
+                parent.spawn(label_node("Nothing here", 16.0, Color::srgb(0.6, 0.6, 0.6)));
+            }
+
+            let first = view.scroll * columns;
+            let last = (first + columns * visible_rows).min(stacks.len());
+            for row in stacks[first..last].chunks(columns) {
+                parent
+                    .spawn(Node {
+                        column_gap: Val::Px(6.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        for stack in row {
+                            spawn_stack(parent, stack, view.layout, &view.selected);
+                        }
+                    });
+            }
+
+            if max_scroll > 0 {
+                parent.spawn((
+                    label_node(
+                        format!("{}/{}  (scroll)", view.scroll + 1, max_scroll + 1),
+                        12.0,
+                        Color::srgb(0.6, 0.6, 0.6),
+                    ),
+                    Node {
+                        margin: UiRect::top(Val::Auto),
+                        ..default()
+                    },
+                ));
+            }
+        });
+
+        let selected_stack = view.selected.as_ref().and_then(|(name, rarity)| {
+            stacks
+                .iter()
+                .find(|stack| &stack.item.name == name && stack.item.rarity == *rarity)
+        });
+        let detail_text = match selected_stack {
+            Some(stack) => format!(
+                "{}\n\n{:?} {}\nOwned: {}\n\n{}",
+                stack.item.name,
+                stack.item.rarity,
+                stack.item.category().name(),
+                stack.count,
+                stack.item.description()
+            ),
+            None => "Select an item to see its details".to_string(),
+        };
+        commands.entity(detail).despawn_descendants();
+        commands.entity(detail).with_child((
+            Text::new(detail_text),
+            TextFont::from_font_size(13.0),
+            TextColor(Color::WHITE),
+        ));
+    }
+
+    fn spawn_stack(
+        parent: &mut ChildBuilder,
+        stack: &ItemStack,
+        layout: InventoryLayout,
+        selected: &Option<(String, crate::LootRarity)>,
+    ) {
+        let key = (stack.item.name.clone(), stack.item.rarity);
+        let (size, label) = match layout {
+            InventoryLayout::List => (
+                Vec2::new(360.0, LIST_ROW_HEIGHT - 6.0),
+                if stack.count > 1 {
+                    format!("{} x{}", stack.item.name, stack.count)
+                } else {
+                    stack.item.name.clone()
+                },
+            ),
+            InventoryLayout::Grid => (
+                GRID_CELL_SIZE,
+                format!("{}\nx{}", stack.item.name, stack.count),
+            ),
+        };
+
+        let mut entry = parent.spawn(box_node(size, stack.item.rarity.get_color()));
+        if selected.as_ref() == Some(&key) {
+            entry.insert(Outline::new(Val::Px(2.0), Val::ZERO, Color::WHITE));
+        }
+        entry
+            .with_child(label_node(label, 13.0, stack.item.rarity.get_text_color()))
+            .observe(select_item_on::<Pointer<Click>>(key));
+    }
+
+    pub fn handle_inventory_scroll(
+        mut view: ResMut<InventoryView>,
+        mut mouse_wheel: EventReader<MouseWheel>,
+        actions: Res<ActionInput>,
+    ) {
+        let mut scroll_direction: f32 = mouse_wheel.read().map(|event| event.y).sum();
+
+        if actions.just_pressed(Action::NavigateUp) {
+            scroll_direction += 1.0;
+        }
+        if actions.just_pressed(Action::NavigateDown) {
+            scroll_direction -= 1.0;
+        }
+
+        // The refresh clamps the upper bound once it knows how many stacks there are
+        if scroll_direction > 0.0 && view.scroll > 0 {
+            view.scroll -= 1;
+        } else if scroll_direction < 0.0 {
+            view.scroll += 1;
+        }
+    }
+
+    fn toolbar_on<E: Debug + Clone + Reflect>(
+        button: ToolbarButton,
+    ) -> impl Fn(Trigger<E>, ResMut<InventoryView>) {
+        move |_ev, mut view| {
+            match button {
+                ToolbarButton::Layout => view.layout = view.layout.next(),
+                ToolbarButton::Sort => view.sort = view.sort.next(),
+                ToolbarButton::Filter => view.filter = view.filter.next(),
+            }
+            view.scroll = 0;
+        }
+    }
+
+    fn select_item_on<E: Debug + Clone + Reflect>(
+        key: (String, crate::LootRarity),
+    ) -> impl Fn(Trigger<E>, ResMut<InventoryView>) {
+        move |_ev, mut view| {
+            view.selected = Some(key.clone());
+        }
+    }
+}
@@ -0,0 +1,140 @@
+pub mod layout {
+    use bevy::{
+        prelude::*,
+        window::{PrimaryWindow, WindowResized},
+    };
+
+    use crate::GameConfig;
+
+    /// Pins a sprite to a point on the screen, so it stays in place when the window is
+    /// resized. `anchor` runs from -1 to 1 on each axis: (0, 0) is the centre of the screen
+    /// and (1, 1) the top right corner. `offset` is added on top, in pixels at a UI scale
+    /// of 1.
+    #[derive(Component, Clone, Copy, Debug)]
+    pub struct ScreenAnchor {
+        pub anchor: Vec2,
+        pub offset: Vec2,
+    }
+
+    impl ScreenAnchor {
+        pub fn new(anchor: Vec2, offset: Vec2) -> Self {
+            ScreenAnchor { anchor, offset }
+        }
+
+        pub fn top(offset_y: f32) -> Self {
+            ScreenAnchor::new(Vec2::Y, Vec2::new(0.0, offset_y))
+        }
+
+        pub fn bottom(offset_y: f32) -> Self {
+            ScreenAnchor::new(Vec2::NEG_Y, Vec2::new(0.0, offset_y))
+        }
+
+        pub fn position(&self, game_config: &GameConfig, ui_scale: f32) -> Vec2 {
+            self.anchor * game_config.size() / 2.0 + self.offset * ui_scale
+        }
+    }
+
+    /// A sprite stretched over the whole screen, like the backdrop of a menu.
+    #[derive(Component, Default)]
+    pub struct FullScreen;
+
+    /// A UI root covering the whole window, with its children stacked in a centred column.
+    pub fn screen_node(color: Color) -> (Node, BackgroundColor) {
+        (
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(color),
+        )
+    }
+
+    /// A fixed-size UI box with its contents centred, for buttons and list entries.
+    pub fn box_node(size: Vec2, color: Color) -> (Node, BackgroundColor) {
+        (
+            Node {
+                width: Val::Px(size.x),
+                height: Val::Px(size.y),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(color),
+        )
+    }
+
+    /// Text inside a UI box. It lets clicks through to the box, so hovering the label doesn't
+    /// count as leaving the button.
+    pub fn label_node(
+        text: impl Into<String>,
+        font_size: f32,
+        color: Color,
+    ) -> (Text, TextFont, TextColor, TextLayout, PickingBehavior) {
+        (
+            Text::new(text),
+            TextFont::from_font_size(font_size),
+            TextColor(color),
+            TextLayout::new_with_justify(JustifyText::Center),
+            PickingBehavior::IGNORE,
+        )
+    }
+
+    /// Keeps the screen size in `GameConfig` in step with the primary window.
+    pub fn track_window_size(
+        mut resized: EventReader<WindowResized>,
+        window_query: Query<Entity, With<PrimaryWindow>>,
+        mut game_config: ResMut<GameConfig>,
+    ) {
+        let Ok(primary) = window_query.get_single() else {
+            resized.clear();
+            return;
+        };
+        let Some(event) = resized
+            .read()
+            .filter(|event| event.window == primary)
+            .last()
+        else {
+            return;
+        };
+        if game_config.screen_width != event.width || game_config.screen_height != event.height {
+            game_config.screen_width = event.width;
+            game_config.screen_height = event.height;
+        }
+    }
+
+    /// Moves anchored sprites when they first appear and whenever the screen size changes.
+    /// They're also sized by the same `UiScale` as the UI nodes, so the setting reaches the
+    /// battle HUD too.
+    pub fn apply_screen_anchors(
+        game_config: Res<GameConfig>,
+        ui_scale: Res<UiScale>,
+        mut anchor_query: Query<(Ref<ScreenAnchor>, &mut Transform)>,
+    ) {
+        let resized = game_config.is_changed() || ui_scale.is_changed();
+        for (anchor, mut transform) in anchor_query.iter_mut() {
+            if !resized && !anchor.is_changed() {
+                continue;
+            }
+            let position = anchor.position(&game_config, ui_scale.0);
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+            transform.scale = Vec3::new(ui_scale.0, ui_scale.0, 1.0);
+        }
+    }
+
+    pub fn fit_full_screen(
+        game_config: Res<GameConfig>,
+        mut sprite_query: Query<(Ref<FullScreen>, &mut Sprite)>,
+    ) {
+        for (full_screen, mut sprite) in sprite_query.iter_mut() {
+            if game_config.is_changed() || full_screen.is_added() {
+                sprite.custom_size = Some(game_config.size());
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+pub mod loot {
+    use std::collections::HashMap;
+
+    use bevy::prelude::*;
+    use rand::random_range;
+    use serde::Deserialize;
+
+    use crate::card::card::{CardEffect, Deck};
+    use crate::dungeon::dungeon::{DungeonRank, EnemyKind};
+    use crate::relics::relics::{Relic, Relics};
+    use crate::{Inventory, LootItem, LootRarity};
+
+    const RARITIES: [LootRarity; 3] = [LootRarity::Common, LootRarity::Rare, LootRarity::Epic];
+
+    #[derive(Deserialize, Clone, Debug)]
+    #[serde(tag = "type")]
+    enum RewardKind {
+        Gold { min: u32, max: u32 },
+        Item { name: String },
+        Card,
+        Relic,
+    }
+
+    #[derive(Deserialize)]
+    struct LootEntry {
+        weight: u32,
+        reward: RewardKind,
+    }
+
+    #[derive(Deserialize)]
+    struct EnemyLootTable {
+        rolls: (usize, usize),
+        guaranteed: Vec<RewardKind>,
+        entries: Vec<LootEntry>,
+    }
+
+    #[derive(Deserialize)]
+    struct RankScaling {
+        rarity_weights: [u32; 3],
+        gold_multiplier: f32,
+        bonus_rolls: usize,
+    }
+
+    /// Drop tables loaded from `assets/loot_tables.json`.
+    #[derive(Resource, Deserialize)]
+    pub struct LootTables {
+        /// Item rolls without an epic before the next one is forced to be epic.
+        pity_threshold: usize,
+        ranks: HashMap<DungeonRank, RankScaling>,
+        enemies: HashMap<EnemyKind, EnemyLootTable>,
+    }
+
+    impl Default for LootTables {
+        fn default() -> Self {
+            serde_json::from_str(include_str!("../assets/loot_tables.json"))
+                .expect("assets/loot_tables.json should be a valid loot table")
+        }
+    }
+
+    #[derive(Resource, Default)]
+    pub struct LootPity {
+        rolls_since_epic: usize,
+    }
+
+    #[derive(Component, Clone, Debug)]
+    pub enum Reward {
+        Gold(u32),
+        Item(LootItem),
+        Card(CardEffect),
+        Relic(Relic),
+    }
+
+    impl Reward {
+        pub fn label(&self) -> String {
+            match self {
+                Reward::Gold(amount) => format!("{} Gold", amount),
+                Reward::Item(item) => item.name.clone(),
+                Reward::Card(effect) => format!("Card: {}", effect.name()),
+                Reward::Relic(relic) => format!("Relic: {}", relic.name),
+            }
+        }
+
+        /// Hands the reward to whichever part of the run state it belongs to.
+        pub fn claim(self, inventory: &mut Inventory, deck: &mut Deck, relics: &mut Relics) {
+            match self {
+                Reward::Gold(amount) => inventory.gold += amount,
+                Reward::Item(item) => inventory.items.push(item),
+                Reward::Card(effect) => deck.cards.push(effect),
+                Reward::Relic(relic) => relics.relics.push(relic),
+            }
+        }
+
+        /// Only items take up room in the inventory; gold, cards and relics go elsewhere.
+        pub fn needs_inventory_slot(&self) -> bool {
+            matches!(self, Reward::Item(_))
+        }
+
+        pub fn rarity(&self) -> LootRarity {
+            match self {
+                Reward::Gold(_) => LootRarity::Common,
+                Reward::Item(item) => item.rarity,
+                Reward::Card(_) => LootRarity::Rare,
+                Reward::Relic(_) => LootRarity::Epic,
+            }
+        }
+    }
+
+    pub fn pick_weighted<T>(items: &[T], weight: impl Fn(&T) -> u32) -> Option<&T> {
+        let total: u32 = items.iter().map(&weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut pick = random_range(0..total);
+        items.iter().find(|item| {
+            let item_weight = weight(item);
+            if pick < item_weight {
+                true
+            } else {
+                pick -= item_weight;
+                false
+            }
+        })
+    }
+
+    impl LootTables {
+        pub fn roll(
+            &self,
+            enemy: EnemyKind,
+            rank: DungeonRank,
+            pity: &mut LootPity,
+        ) -> Vec<Reward> {
+            let table = &self.enemies[&enemy];
+            let scaling = &self.ranks[&rank];
+
+            let mut rewards: Vec<Reward> = table
+                .guaranteed
+                .iter()
+                .map(|kind| self.resolve(kind, scaling, pity))
+                .collect();
+
+            let rolls = random_range(table.rolls.0..=table.rolls.1) + scaling.bonus_rolls;
+            for _ in 0..rolls {
+                if let Some(entry) = pick_weighted(&table.entries, |entry| entry.weight) {
+                    rewards.push(self.resolve(&entry.reward, scaling, pity));
+                }
+            }
+
+            // Several gold drops read better as a single pile
+            let gold: u32 = rewards
+                .iter()
+                .map(|reward| match reward {
+                    Reward::Gold(amount) => *amount,
+                    _ => 0,
+                })
+                .sum();
+            rewards.retain(|reward| !matches!(reward, Reward::Gold(_)));
+            if gold > 0 {
+                rewards.insert(0, Reward::Gold(gold));
+            }
+            rewards
+        }
+
+        fn resolve(&self, kind: &RewardKind, scaling: &RankScaling, pity: &mut LootPity) -> Reward {
+            match kind {
+                RewardKind::Gold { min, max } => {
+                    let amount = random_range(*min..=*max) as f32 * scaling.gold_multiplier;
+                    Reward::Gold(amount.round() as u32)
+                }
+                RewardKind::Item { name } => Reward::Item(LootItem {
+                    name: name.clone(),
+                    rarity: self.roll_rarity(scaling, pity),
+                }),
+                RewardKind::Card => Reward::Card(CardEffect::get_random_effect()),
+                RewardKind::Relic => Reward::Relic(Relic::get_random_relic()),
+            }
+        }
+
+        fn roll_rarity(&self, scaling: &RankScaling, pity: &mut LootPity) -> LootRarity {
+            let rarity = if pity.rolls_since_epic >= self.pity_threshold {
+                LootRarity::Epic
+            } else {
+                let weighted: Vec<(LootRarity, u32)> = RARITIES
+                    .iter()
+                    .copied()
+                    .zip(scaling.rarity_weights.iter().copied())
+                    .collect();
+                pick_weighted(&weighted, |(_, weight)| *weight)
+                    .map(|(rarity, _)| *rarity)
+                    .unwrap_or(LootRarity::Common)
+            };
+
+            if matches!(rarity, LootRarity::Epic) {
+                pity.rolls_since_epic = 0;
+            } else {
+                pity.rolls_since_epic += 1;
+            }
+            rarity
+        }
+    }
+}
@@ -1,34 +1,135 @@
 //! Demonstrates picking for sprites and sprite atlases. The picking backend only tests against the
 //! sprite bounds, so the sprite atlas can be picked by clicking on its transparent areas.
 
-use bevy::{prelude::*, state::commands, ui::Interaction, window::WindowResolution};
+use bevy::{input::InputSystem, prelude::*, state::commands};
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
-use card::card::{ActiveEffect, CardEffect, Effects};
+use card::card::{ActiveEffect, CardEffect, Deck, Effects};
+use card_play::card_play::{
+    cancel_pending_target, drag_card_end_on, drag_card_on, drag_card_start_on, drop_card_on,
+    play_card_hotkeys, play_card_on, spawn_play_mode_text, target_enemy_on, toggle_play_mode,
+    update_target_highlight, CardPlaySettings, PendingTarget, PlayMode, ReadyToPlay,
+    READY_TO_PLAY_COLOR,
+};
+use card_reward::card_reward::{despawn_card_reward, spawn_card_reward};
+use card_tooltip::card_tooltip::{
+    despawn_card_tooltips, hover_card_on, hover_card_out, inspect_card_on,
+};
+use combat::combat::{
+    play_card, take_damage, CombatEvent, Combatant, CombatantEffects, CombatantStats, Shield,
+};
+use combat_log::combat_log::{
+    despawn_combat_log, handle_combat_log_input, record_combat_log, spawn_combat_log,
+    update_combat_log_panel, CombatLog,
+};
+use consumables::consumables::{
+    despawn_potion_belt, handle_potion_hotkeys, spawn_potion_belt, update_potion_belt,
+};
+use controls::controls::{
+    capture_rebinding, despawn_controls_screen, refresh_controls_screen, spawn_controls_screen,
+};
+use dungeon::dungeon::{Dungeon, EnemyKind};
+use floating_text::floating_text::{animate_floating_text, show_combat_text};
+use focus::focus::{
+    confirm_focus, navigate_focus, spawn_focus_cursor, update_focus_cursor, Focus, Focusable,
+};
+use health_bar::health_bar::{add_health_bar, update_health_bars};
+use input::input::{read_actions, Action, ActionInput, InputBindings, Rebinding};
+use inventory_screen::inventory_screen::{
+    handle_inventory_scroll, refresh_inventory_screen, spawn_inventory_screen, InventoryView,
+};
+use layout::layout::{
+    apply_screen_anchors, box_node, fit_full_screen, label_node, screen_node, track_window_size,
+    ScreenAnchor,
+};
+use loot::loot::{LootPity, LootTables, Reward};
+use main_menu::main_menu::{
+    despawn_main_menu, despawn_pause_menu, despawn_title_screen, leave_title_screen,
+    spawn_main_menu, spawn_pause_menu, spawn_title_screen, toggle_pause, PauseState, RunStatus,
+};
+use mana::mana::{
+    add_mana_bar, refill_mana, regenerate_mana, update_mana_bar, Mana, WaitingForMana,
+};
+use narrative::narrative::{
+    despawn_event_screen, refresh_event_screen, spawn_event_screen, NarrativeEvents,
+};
+use new_run::new_run::{despawn_class_select, spawn_class_select, start_new_run};
+use progression::progression::{
+    award_experience, despawn_level_up_screen, refresh_level_up_screen, spawn_level_text,
+    spawn_level_up_screen, update_level_text, Experience,
+};
 use rand::random_range;
+use relics::relics::{trigger_relics, Relics};
+use rest_site::rest_site::{despawn_rest_site, refresh_rest_site, spawn_rest_site};
+use rng::rng::RunRng;
+use run_stats::run_stats::{
+    despawn_profile_screen, finish_run, in_run, record_run_stats, spawn_profile_screen,
+    spawn_run_summary, tick_play_time, RunHistory, RunStats,
+};
+use serde::Deserialize;
+use settings::settings::{
+    apply_settings, despawn_settings_screen, inspector_enabled, refresh_settings_screen,
+    spawn_settings_screen, Settings,
+};
+use shop::shop::{close_shop, open_shop, refresh_shop_screen};
 use skills::skills::{Class, Stats};
+use status_icons::status_icons::{add_status_icon_row, update_status_icons};
 use std::fmt::Debug;
 
 mod card;
+mod card_play;
+mod card_reward;
+mod card_tooltip;
+mod combat;
+mod combat_log;
+mod consumables;
+mod controls;
+mod dungeon;
+mod floating_text;
+mod focus;
+mod health_bar;
+mod input;
+mod inventory_screen;
+mod layout;
+mod loot;
+mod main_menu;
+mod mana;
+mod narrative;
+mod new_run;
+mod progression;
+mod relics;
+mod rest_site;
+mod rng;
+mod run_stats;
+mod settings;
+mod shop;
 mod skills;
+mod status_icons;
 
 const MENU_ALPHA: f32 = 0.8;
-const MENU_Z_LAYER: f32 = 1.1;
-const INVENTORY_ITEM_HEIGHT: f32 = 50.0;
-const INVENTORY_VISIBLE_ITEMS: f32 = 8.0; // Number of items visible at once
-const SCROLL_SPEED: f32 = 20.0;
+const INVENTORY_CAPACITY: usize = 12;
 
+/// Size of the window in world units, kept up to date as it's resized.
 #[derive(Resource, Debug, Default)]
 struct GameConfig {
     screen_width: f32,
     screen_height: f32,
 }
 
+impl GameConfig {
+    fn size(&self) -> Vec2 {
+        Vec2::new(self.screen_width, self.screen_height)
+    }
+}
+
 #[derive(Component, Reflect)]
 struct EnemyHealth(i32);
 
 #[derive(Component, Reflect)]
 struct PlayerHealth(i32);
 
+#[derive(Component, Reflect)]
+struct MaxHealth(i32);
+
 #[derive(Component, Reflect)]
 struct PlayerEntity;
 
@@ -37,25 +138,37 @@ struct CardAttackTimer(Timer);
 
 pub const RED: Color = Color::srgb(1.0, 0.0, 0.0);
 pub const YELLOW: Color = Color::srgb(1.0, 1.0, 0.0);
+const WAITING_FOR_MANA_COLOR: Color = Color::srgb(0.4, 0.4, 0.7);
 pub const GREEN: Color = Color::srgb(0.0, 1.0, 0.0);
 
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 enum GameState {
     #[default]
+    Title,
+    MainMenu,
+    Profile,
+    Settings,
+    Controls,
+    ClassSelect,
     Battle,
     LootScreen,
+    LevelUp,
+    CardReward,
+    Shop,
+    RestSite,
+    Event,
     Menu,
     EndBattle,
     GameOver,
 }
 
-#[derive(Component, Clone, Reflect)]
+#[derive(Component, Clone, Debug, Reflect)]
 struct LootItem {
     name: String,
     rarity: LootRarity,
 }
 
-#[derive(Component, Clone, Copy, Reflect)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Reflect, Deserialize)]
 enum LootRarity {
     Common,
     Rare,
@@ -71,6 +184,14 @@ impl LootRarity {
         }
     }
 
+    fn price(&self) -> u32 {
+        match self {
+            LootRarity::Common => 40,
+            LootRarity::Rare => 90,
+            LootRarity::Epic => 160,
+        }
+    }
+
     fn get_text_color(&self) -> Color {
         match self {
             LootRarity::Common => Color::rgb(0.2, 0.2, 0.2),
@@ -80,9 +201,72 @@ impl LootRarity {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ItemCategory {
+    Consumable,
+    Weapon,
+    Misc,
+}
+
+impl ItemCategory {
+    fn name(&self) -> &'static str {
+        match self {
+            ItemCategory::Consumable => "Consumable",
+            ItemCategory::Weapon => "Weapon",
+            ItemCategory::Misc => "Misc",
+        }
+    }
+}
+
+impl LootItem {
+    fn category(&self) -> ItemCategory {
+        if self.consumable_effect().is_some() {
+            return ItemCategory::Consumable;
+        }
+        match self.name.as_str() {
+            "Magic Sword" => ItemCategory::Weapon,
+            _ => ItemCategory::Misc,
+        }
+    }
+
+    fn description(&self) -> String {
+        if let Some(effect) = self.consumable_effect() {
+            return format!(
+                "Use in battle from the potion belt.\n{}.",
+                effect.describe()
+            );
+        }
+        match self.name.as_str() {
+            "Magic Sword" => {
+                "A blade humming with mana. Rarer ones fetch a better price.".to_string()
+            }
+            _ => "Nothing special about it.".to_string(),
+        }
+    }
+
+    /// The effect of using this item in battle, if it is a consumable.
+    fn consumable_effect(&self) -> Option<CardEffect> {
+        match self.name.as_str() {
+            "Health Potion" => Some(CardEffect::Heal(match self.rarity {
+                LootRarity::Common => 15,
+                LootRarity::Rare => 25,
+                LootRarity::Epic => 40,
+            })),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Resource, Default)]
 struct Inventory {
     items: Vec<LootItem>,
+    gold: u32,
+}
+
+impl Inventory {
+    fn has_room(&self) -> bool {
+        self.items.len() < INVENTORY_CAPACITY
+    }
 }
 
 #[derive(Component)]
@@ -94,14 +278,14 @@ struct InventoryDisplay;
 #[derive(Component)]
 struct LootAllButton;
 
-fn debug_display_state(state: Res<State<GameState>>, input: Res<ButtonInput<KeyCode>>) {
-    if input.just_pressed(KeyCode::Escape) {
-        println!("Current state: {:?}", state.get());
-    }
-    if input.just_pressed(KeyCode::KeyQ) {
-        println!("Q pressed");
+#[derive(Component)]
+struct GoldText;
+
+fn quit_game(actions: Res<ActionInput>, mut exit: EventWriter<AppExit>) {
+    if actions.just_pressed(Action::Quit) {
+        println!("Quit pressed");
         // quit the game
-        std::process::exit(0);
+        exit.send(AppExit::Success);
     }
 }
 
@@ -111,10 +295,14 @@ struct EnemyEntity;
 #[derive(Bundle)]
 struct EnemyBundle {
     enemy: EnemyEntity,
+    kind: EnemyKind,
     name: Name,
     sprite: Sprite,
     transform: Transform,
+    anchor: ScreenAnchor,
     enemy_health: EnemyHealth,
+    max_health: MaxHealth,
+    shield: Shield,
     stats: Stats,
     effects: Effects,
 }
@@ -127,15 +315,43 @@ enum CardPosition {
 fn on_enter_battle(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    game_config: Res<GameConfig>,
     enemy_query: Query<Entity, With<EnemyEntity>>,
+    player_query: Query<Entity, With<PlayerEntity>>,
+    player_cards_query: Query<Entity, With<PlayerCard>>,
+    mut player_shield_query: Query<&mut Shield, With<PlayerEntity>>,
+    deck: Res<Deck>,
+    mut dungeon: ResMut<Dungeon>,
+    mut combat_events: EventWriter<CombatEvent>,
 ) {
     if let Ok(enemy) = enemy_query.get_single() {
         println!("Enemy already exists");
         return;
     }
-    let enemybundle = spawn_new_enemy(asset_server.load("boss_bee.png"), &game_config);
-    let enemyid = commands.spawn(enemybundle).id();
+    let kind = EnemyKind::get_random_kind();
+    dungeon.encounter = kind;
+    let enemybundle = spawn_new_enemy(asset_server.load("boss_bee.png"), kind);
+    let enemyid = commands
+        .spawn(enemybundle)
+        .insert(Focusable)
+        .observe(target_enemy_on())
+        .observe(drop_card_on())
+        .id();
+    combat_events.send(CombatEvent::BattleStarted);
+
+    // Shields only last for the fight they were raised in
+    for mut shield in player_shield_query.iter_mut() {
+        shield.0 = 0;
+    }
+
+    // The deck may have changed since the last fight, so rebuild the player's cards from it
+    if let Ok(player) = player_query.get_single() {
+        for card in player_cards_query.iter() {
+            commands.entity(card).despawn_recursive();
+        }
+        commands.entity(player).with_children(|parent| {
+            spawn_player_cards(parent, &asset_server, &deck, Vec2::splat(128.0 / 2.0));
+        });
+    }
 
     let num_cards = random_range(1..4);
     commands.entity(enemyid).with_children(|parent| {
@@ -156,23 +372,29 @@ fn on_enter_battle(
             Transform::from_xyz(0.0, -20.0, 0.1),
             EnemyHealthText,
         ));
+        let half_size = 128.0 / 4.0 * kind.sprite_scale();
+        add_health_bar(parent, half_size + 8.0);
+        add_status_icon_row(parent, Vec2::new(half_size + 16.0, 0.0));
     });
 }
 
-fn spawn_new_enemy(image: Handle<Image>, game_config: &GameConfig) -> EnemyBundle {
-    println!("Spawning new enemy");
+fn spawn_new_enemy(image: Handle<Image>, kind: EnemyKind) -> EnemyBundle {
+    println!("Spawning new enemy: {}", kind.name());
     let sprite_size = Vec2::splat(128.0 / 2.0);
     let enemy = EnemyBundle {
         enemy: EnemyEntity,
-        name: Name::new("Enemy Bundle"),
+        kind,
+        name: Name::new(kind.name()),
         sprite: Sprite {
             image,
-            custom_size: Some(sprite_size),
+            custom_size: Some(sprite_size * kind.sprite_scale()),
             ..default()
         },
-        transform: Transform::from_xyz(0.0, game_config.screen_height / 2.0 + -sprite_size.y, 0.1)
-            .with_scale(Vec3::splat(1.0)),
-        enemy_health: EnemyHealth(100),
+        transform: Transform::from_xyz(0.0, 0.0, 0.1).with_scale(Vec3::splat(1.0)),
+        anchor: ScreenAnchor::top(-sprite_size.y),
+        enemy_health: EnemyHealth(kind.max_health()),
+        max_health: MaxHealth(kind.max_health()),
+        shield: Shield::default(),
         stats: Stats {
             strength: 10,
             agility: 10,
@@ -189,27 +411,45 @@ fn spawn_new_enemy(image: Handle<Image>, game_config: &GameConfig) -> EnemyBundl
 }
 
 fn update_enemy_health(
-    mut enemy_query: Query<(&mut EnemyHealth)>,
+    mut enemy_query: Query<(&mut EnemyHealth, &MaxHealth)>,
     mut enemy_health_text_query: Query<(&Parent, &mut Text2d), With<EnemyHealthText>>,
 ) {
     for (parent, mut health_text) in enemy_health_text_query.iter_mut() {
-        let Ok(mut health) = enemy_query.get_mut(parent.get()) else {
+        let Ok((mut health, max_health)) = enemy_query.get_mut(parent.get()) else {
             println!("No health text found");
             return;
         };
-        health_text.0 = format!("{}", health.0);
+        health_text.0 = format!("{}/{}", health.0, max_health.0);
     }
 }
 fn update_player_health(
-    mut player_query: Query<(&mut PlayerHealth)>,
+    mut player_query: Query<(&mut PlayerHealth, &MaxHealth)>,
     mut player_health_text_query: Query<(&Parent, &mut Text2d), With<PlayerHealthText>>,
 ) {
     for (parent, mut health_text) in player_health_text_query.iter_mut() {
-        let Ok(mut health) = player_query.get_mut(parent.get()) else {
+        let Ok((mut health, max_health)) = player_query.get_mut(parent.get()) else {
             println!("No health text found");
             return;
         };
-        health_text.0 = format!("{}", health.0);
+        health_text.0 = format!("{}/{}", health.0, max_health.0);
+    }
+}
+
+/// Keeps max health in line with stamina after training or a level-up. Health goes up by
+/// as much as the max does, so the new points count straight away.
+fn apply_stamina(
+    mut player_query: Query<
+        (&Stats, &mut MaxHealth, &mut PlayerHealth),
+        (With<PlayerEntity>, Changed<Stats>),
+    >,
+) {
+    for (stats, mut max_health, mut health) in player_query.iter_mut() {
+        let gained = stats.max_health() - max_health.0;
+        if gained == 0 {
+            continue;
+        }
+        max_health.0 = stats.max_health();
+        health.0 = (health.0 + gained).clamp(1, max_health.0);
     }
 }
 
@@ -262,13 +502,10 @@ enum CardAnimationState {
 }
 
 /// Set up a scene that tests all sprite anchor types.
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>, game_config: Res<GameConfig>) {
+fn setup(mut commands: Commands, game_config: Res<GameConfig>) {
     println!("Setting up scene");
     commands.spawn(Camera2d);
 
-    let len = 128.0;
-    let sprite_size = Vec2::splat(len / 2.0);
-
     // let cards = [
     //     Card {
     //         sprite: Sprite {
@@ -300,63 +537,8 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, game_config: Re
     //     },
     // ];
 
-    let screen_width = game_config.screen_width;
-    let screen_height = game_config.screen_height;
     println!("Game config: {:?}", game_config);
 
-    let num_cards = random_range(1..4);
-    let starting_x = 0.0 - (num_cards / 2) as f32 * (sprite_size.x * 0.7);
-
-    let mut current_x = starting_x;
-    let playerbundle = spawn_player(asset_server.load("player.png"), sprite_size, screen_height);
-
-    let playerid = commands.spawn(playerbundle).with_children(|parent| {
-        // add health text
-        parent.spawn((
-            Name::new("Health Text"),
-            Text2d::new("100"),
-            Transform::from_xyz(0.0, -sprite_size.y, 0.1),
-            PlayerHealthText,
-        ));
-        // add skills
-
-        for i in 0..num_cards {
-            let transform = get_card_transform((i, num_cards), sprite_size, CardPosition::Top);
-            let cardeffect = CardEffect::get_random_effect();
-            let sprite = cardeffect.get_sprite_path();
-            let sprite_handle = asset_server.load(sprite);
-            parent
-                .spawn((
-                    Name::new(format!("Player Card {}", i)),
-                    Sprite {
-                        image: sprite_handle,
-                        custom_size: Some(sprite_size),
-                        ..default()
-                    },
-                    SelectableCard(true),
-                    transform,
-                    PlayerCard,
-                    cardeffect,
-                    CardAttackTimer(Timer::from_seconds(
-                        random_range(1.0..3.0),
-                        TimerMode::Repeating,
-                    )),
-                    CardAnimation {
-                        start_pos: transform.translation,
-                        offset: 20.0, // How high the card will bounce
-                        state: CardAnimationState::Idle,
-                    },
-                ))
-                // .observe(select_card_on::<Pointer<Click>>())
-                // .observe(hover_card_on::<Pointer<Over>>())
-                // .observe(hover_card_out::<Pointer<Out>>())
-                .with_children(|parent| {
-                    add_timer_bar(parent);
-                });
-            current_x += sprite_size.x * 0.7;
-        }
-    });
-
     // Spawn inventory button
     commands
         .spawn((
@@ -366,11 +548,8 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, game_config: Re
                 custom_size: Some(Vec2::new(40.0, 40.0)),
                 ..default()
             },
-            Transform::from_xyz(
-                -game_config.screen_width / 2.0 + 30.0,
-                game_config.screen_height / 2.0 - 30.0,
-                0.9,
-            ),
+            Transform::from_xyz(0.0, 0.0, 0.9),
+            ScreenAnchor::new(Vec2::new(-1.0, 1.0), Vec2::new(30.0, -30.0)),
             InventoryButton,
         ))
         .with_children(|parent| {
@@ -379,6 +558,97 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, game_config: Re
         .observe(change_sprite_color::<Pointer<Out>>(Color::srgb(
             0.0, 0.7, 0.5,
         )));
+
+    commands.spawn((
+        Name::new("Gold Text"),
+        Text2d::new("Gold: 0"),
+        TextColor(Color::srgb(1.0, 0.85, 0.2)),
+        Transform::from_xyz(0.0, 0.0, 0.95),
+        ScreenAnchor::new(Vec2::ONE, Vec2::new(-70.0, -30.0)),
+        GoldText,
+    ));
+}
+
+/// Spawns a fresh player for a new run. Their cards are added from the `Deck` when the first
+/// battle starts.
+fn spawn_run_player(commands: &mut Commands, asset_server: &AssetServer, class: Class) {
+    let sprite_size = Vec2::splat(128.0 / 2.0);
+    let playerbundle = spawn_player(asset_server.load("player.png"), sprite_size, class);
+
+    commands
+        .spawn(playerbundle)
+        .with_children(|parent| {
+            // add health text
+            parent.spawn((
+                Name::new("Health Text"),
+                Text2d::new("100"),
+                Transform::from_xyz(0.0, -sprite_size.y, 0.1),
+                PlayerHealthText,
+            ));
+            add_health_bar(parent, -sprite_size.y / 2.0 - 6.0);
+            add_mana_bar(parent, -sprite_size.y / 2.0 - 16.0);
+            add_status_icon_row(parent, Vec2::new(sprite_size.x / 2.0 + 16.0, 0.0));
+        })
+        .observe(drop_card_on());
+}
+
+fn update_gold_text(
+    inventory: Res<Inventory>,
+    mut gold_text_query: Query<&mut Text2d, With<GoldText>>,
+) {
+    if !inventory.is_changed() {
+        return;
+    }
+    for mut text in gold_text_query.iter_mut() {
+        text.0 = format!("Gold: {}", inventory.gold);
+    }
+}
+
+fn spawn_player_cards(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    deck: &Deck,
+    sprite_size: Vec2,
+) {
+    let num_cards = deck.cards.len() as i32;
+    for (i, cardeffect) in deck.cards.iter().enumerate() {
+        let transform = get_card_transform((i as i32, num_cards), sprite_size, CardPosition::Top);
+        let sprite = cardeffect.get_sprite_path();
+        let sprite_handle = asset_server.load(sprite);
+        parent
+            .spawn((
+                Name::new(format!("Player Card {}", i)),
+                Sprite {
+                    image: sprite_handle,
+                    custom_size: Some(sprite_size),
+                    ..default()
+                },
+                SelectableCard(true),
+                transform,
+                PlayerCard,
+                Focusable,
+                cardeffect.clone(),
+                CardAttackTimer(Timer::from_seconds(
+                    random_range(1.0..3.0),
+                    TimerMode::Repeating,
+                )),
+                CardAnimation {
+                    start_pos: transform.translation,
+                    offset: 20.0, // How high the card will bounce
+                    state: CardAnimationState::Idle,
+                },
+            ))
+            .observe(play_card_on())
+            .observe(drag_card_start_on())
+            .observe(drag_card_on())
+            .observe(drag_card_end_on())
+            .observe(hover_card_on::<Pointer<Over>>())
+            .observe(hover_card_out::<Pointer<Out>>())
+            .observe(inspect_card_on())
+            .with_children(|parent| {
+                add_timer_bar(parent);
+            });
+    }
 }
 
 fn get_card_transform(
@@ -417,7 +687,7 @@ fn add_card(
         CardEffect::DirectDamage(_) => asset_server.load("direct.png"),
         CardEffect::DamageOverTime { .. } => asset_server.load("dot.png"),
         CardEffect::Stun { .. } => asset_server.load("stun.png"),
-        CardEffect::Heal(_) => asset_server.load("heal.png"),
+        CardEffect::Heal(_) | CardEffect::Shield(_) => asset_server.load("heal.png"),
     };
 
     parent
@@ -439,6 +709,9 @@ fn add_card(
             },
             BattleEntity,
         ))
+        .observe(hover_card_on::<Pointer<Over>>())
+        .observe(hover_card_out::<Pointer<Out>>())
+        .observe(inspect_card_on())
         .with_children(|parent| {
             add_timer_bar(parent);
         });
@@ -470,14 +743,26 @@ struct PlayerBundle {
     name: Name,
     sprite: Sprite,
     transform: Transform,
+    anchor: ScreenAnchor,
     player_health: PlayerHealth,
+    max_health: MaxHealth,
+    shield: Shield,
     stats: Stats,
+    mana: Mana,
     class: Class,
     effects: Effects,
 }
 
-fn spawn_player(image: Handle<Image>, sprite_size: Vec2, screen_height: f32) -> PlayerBundle {
+fn spawn_player(image: Handle<Image>, sprite_size: Vec2, class: Class) -> PlayerBundle {
     println!("Spawning player");
+    let stats = Stats {
+        strength: 20,
+        agility: 10,
+        stamina: 10,
+        perception: 10,
+        intelligence: 10,
+    };
+    let mana = Mana::from_stats(&stats);
     let player = PlayerBundle {
         player: PlayerEntity,
         name: Name::new("Player"),
@@ -486,17 +771,14 @@ fn spawn_player(image: Handle<Image>, sprite_size: Vec2, screen_height: f32) ->
             custom_size: Some(sprite_size),
             ..default()
         },
-        transform: Transform::from_xyz(0.0, -screen_height / 2.0 + sprite_size.y, 0.1)
-            .with_scale(Vec3::splat(1.0)),
-        player_health: PlayerHealth(100),
-        stats: Stats {
-            strength: 20,
-            agility: 10,
-            stamina: 10,
-            perception: 10,
-            intelligence: 10,
-        },
-        class: Class::Warrior,
+        transform: Transform::from_xyz(0.0, 0.0, 0.1).with_scale(Vec3::splat(1.0)),
+        anchor: ScreenAnchor::bottom(sprite_size.y),
+        player_health: PlayerHealth(stats.max_health()),
+        max_health: MaxHealth(stats.max_health()),
+        shield: Shield::default(),
+        stats,
+        mana,
+        class,
         effects: Effects {
             effects: Vec::new(),
         },
@@ -521,11 +803,17 @@ struct CardTimerBar;
 
 fn calculate_player_effects(
     time: Res<Time>,
-    mut player_effect_query: Query<(&mut Effects, &mut PlayerHealth), With<PlayerEntity>>,
+    mut player_effect_query: Query<
+        (&mut Effects, &mut PlayerHealth, &MaxHealth, &mut Shield),
+        With<PlayerEntity>,
+    >,
+    mut combat_events: EventWriter<CombatEvent>,
 ) {
     // tick each of the effect timers
     let mut continued_effects: Vec<ActiveEffect> = vec![];
-    let Ok((mut effects, mut player_health)) = player_effect_query.get_single_mut() else {
+    let Ok((mut effects, mut player_health, max_health, mut shield)) =
+        player_effect_query.get_single_mut()
+    else {
         println!("[calculate_effect_damage] No effects or player health found");
         return;
     };
@@ -540,24 +828,64 @@ fn calculate_player_effects(
                 duration.tick(time.delta());
                 frequency.tick(time.delta());
                 if frequency.finished() {
-                    player_health.0 -= *damage;
+                    take_damage(
+                        Combatant::Player,
+                        *damage,
+                        true,
+                        &mut player_health.0,
+                        &mut shield,
+                        &mut combat_events,
+                    );
                     // frequency.reset();
                 }
                 if !duration.finished() {
                     continued_effects.push(effect.clone());
+                } else {
+                    combat_events.send(CombatEvent::StatusExpired {
+                        target: Combatant::Player,
+                        name: "Damage Over Time",
+                    });
                 }
             }
             ActiveEffect::DirectDamage(damage) => {
-                player_health.0 -= *damage;
+                take_damage(
+                    Combatant::Player,
+                    *damage,
+                    false,
+                    &mut player_health.0,
+                    &mut shield,
+                    &mut combat_events,
+                );
             }
             ActiveEffect::Stun { duration } => {
                 duration.tick(time.delta());
                 if !duration.finished() {
                     continued_effects.push(effect.clone());
+                } else {
+                    combat_events.send(CombatEvent::StatusExpired {
+                        target: Combatant::Player,
+                        name: "Stun",
+                    });
                 }
             }
             ActiveEffect::Heal(heal) => {
-                player_health.0 += *heal;
+                let before = player_health.0;
+                player_health.0 = (player_health.0 + *heal).min(max_health.0);
+                // Only report what was actually restored, not what was clamped off at max
+                let healed = player_health.0 - before;
+                if healed > 0 {
+                    combat_events.send(CombatEvent::Healed {
+                        target: Combatant::Player,
+                        amount: healed,
+                    });
+                }
+            }
+            ActiveEffect::Shield(amount) => {
+                shield.0 += *amount;
+                combat_events.send(CombatEvent::Shielded {
+                    target: Combatant::Player,
+                    amount: *amount,
+                });
             }
         }
     }
@@ -565,11 +893,17 @@ fn calculate_player_effects(
 }
 fn calculate_enemy_effects(
     time: Res<Time>,
-    mut enemy_effect_query: Query<(&mut Effects, &mut EnemyHealth), With<EnemyEntity>>,
+    mut enemy_effect_query: Query<
+        (&mut Effects, &mut EnemyHealth, &MaxHealth, &mut Shield),
+        With<EnemyEntity>,
+    >,
+    mut combat_events: EventWriter<CombatEvent>,
 ) {
     // tick each of the effect timers
     let mut continued_effects: Vec<ActiveEffect> = vec![];
-    let Ok((mut effects, mut enemy_health)) = enemy_effect_query.get_single_mut() else {
+    let Ok((mut effects, mut enemy_health, max_health, mut shield)) =
+        enemy_effect_query.get_single_mut()
+    else {
         println!("[calculate_enemy_effects] No effects or enemy health found");
         return;
     };
@@ -584,24 +918,63 @@ fn calculate_enemy_effects(
                 duration.tick(time.delta());
                 frequency.tick(time.delta());
                 if frequency.finished() {
-                    enemy_health.0 -= *damage;
+                    take_damage(
+                        Combatant::Enemy,
+                        *damage,
+                        true,
+                        &mut enemy_health.0,
+                        &mut shield,
+                        &mut combat_events,
+                    );
                     // frequency.reset();
                 }
                 if !duration.finished() {
                     continued_effects.push(effect.clone());
+                } else {
+                    combat_events.send(CombatEvent::StatusExpired {
+                        target: Combatant::Enemy,
+                        name: "Damage Over Time",
+                    });
                 }
             }
             ActiveEffect::DirectDamage(damage) => {
-                enemy_health.0 -= *damage;
+                take_damage(
+                    Combatant::Enemy,
+                    *damage,
+                    false,
+                    &mut enemy_health.0,
+                    &mut shield,
+                    &mut combat_events,
+                );
             }
             ActiveEffect::Stun { duration } => {
                 duration.tick(time.delta());
                 if !duration.finished() {
                     continued_effects.push(effect.clone());
+                } else {
+                    combat_events.send(CombatEvent::StatusExpired {
+                        target: Combatant::Enemy,
+                        name: "Stun",
+                    });
                 }
             }
             ActiveEffect::Heal(heal) => {
-                enemy_health.0 += *heal;
+                let before = enemy_health.0;
+                enemy_health.0 = (enemy_health.0 + *heal).min(max_health.0);
+                let healed = enemy_health.0 - before;
+                if healed > 0 {
+                    combat_events.send(CombatEvent::Healed {
+                        target: Combatant::Enemy,
+                        amount: healed,
+                    });
+                }
+            }
+            ActiveEffect::Shield(amount) => {
+                shield.0 += *amount;
+                combat_events.send(CombatEvent::Shielded {
+                    target: Combatant::Enemy,
+                    amount: *amount,
+                });
             }
         }
     }
@@ -641,25 +1014,12 @@ fn enemy_auto_attack(
         (&mut CardAttackTimer, &CardEffect, &mut CardAnimation),
         With<EnemyCard>,
     >,
-    mut effect_queries: ParamSet<(
-        Query<&Effects, With<EnemyEntity>>,
-        Query<&mut Effects, With<PlayerEntity>>,
-    )>,
+    stats: CombatantStats,
+    mut rng: ResMut<RunRng>,
+    mut effects: CombatantEffects,
+    mut combat_events: EventWriter<CombatEvent>,
 ) {
-    // First check if enemy is stunned
-    let is_stunned = {
-        let enemy_effects = effect_queries.p0();
-        let Ok(effects) = enemy_effects.get_single() else {
-            println!("[enemy_auto_attack] No enemy effects found");
-            return;
-        };
-        effects
-            .effects
-            .iter()
-            .any(|effect| matches!(effect, ActiveEffect::Stun { duration } if !duration.finished()))
-    };
-
-    if is_stunned {
+    if effects.is_stunned(Combatant::Enemy) {
         // If stunned, don't tick timers and don't allow attacks
         return;
     }
@@ -670,120 +1030,103 @@ fn enemy_auto_attack(
             println!("attack ready");
             animation.state = CardAnimationState::MovingUp;
 
-            let mut player_effects = effect_queries.p1();
-            let Ok(mut effects) = player_effects.get_single_mut() else {
-                println!("[enemy_auto_attack] No player found");
-                return;
-            };
-
-            match effect {
-                CardEffect::DamageOverTime {
-                    damage,
-                    duration,
-                    frequency,
-                } => {
-                    effects.effects.push(ActiveEffect::DamageOverTime {
-                        damage: *damage,
-                        duration: Timer::from_seconds(*duration, TimerMode::Once),
-                        frequency: Timer::from_seconds(*frequency, TimerMode::Repeating),
-                    });
-                }
-                CardEffect::DirectDamage(damage) => {
-                    effects.effects.push(ActiveEffect::DirectDamage(*damage));
-                }
-                CardEffect::Stun { duration } => {
-                    effects.effects.push(ActiveEffect::Stun {
-                        duration: Timer::from_seconds(*duration, TimerMode::Once),
-                    });
-                }
-                CardEffect::Heal(heal) => {
-                    effects.effects.push(ActiveEffect::Heal(*heal));
-                }
-            }
+            play_card(
+                Combatant::Enemy,
+                effect,
+                &stats,
+                &mut rng,
+                &mut effects,
+                &mut combat_events,
+            );
         }
     }
 }
 
 fn player_auto_attack(
     time: Res<Time>,
+    mut commands: Commands,
     mut player_cards_query: Query<
-        (&mut CardAttackTimer, &CardEffect, &mut CardAnimation),
+        (
+            Entity,
+            &mut CardAttackTimer,
+            &CardEffect,
+            &mut CardAnimation,
+            &mut Sprite,
+            Has<WaitingForMana>,
+            Has<ReadyToPlay>,
+        ),
         With<PlayerCard>,
     >,
-    mut effect_queries: ParamSet<(
-        Query<&mut Effects, With<PlayerEntity>>,
-        Query<&mut Effects, With<EnemyEntity>>,
-    )>,
+    settings: Res<CardPlaySettings>,
+    mut mana_query: Query<&mut Mana, With<PlayerEntity>>,
+    stats: CombatantStats,
+    mut rng: ResMut<RunRng>,
+    mut effects: CombatantEffects,
+    mut combat_events: EventWriter<CombatEvent>,
 ) {
-    // First check if player is stunned
-    let is_stunned = {
-        let player_effects = effect_queries.p0();
-        let Ok(effects) = player_effects.get_single() else {
-            println!("[player_auto_attack] No player effects found");
-            return;
-        };
-        effects
-            .effects
-            .iter()
-            .any(|effect| matches!(effect, ActiveEffect::Stun { duration } if !duration.finished()))
-    };
-
-    if is_stunned {
+    if effects.is_stunned(Combatant::Player) {
         // If stunned, don't tick timers and don't allow attacks
         return;
     }
+    let Ok(mut mana) = mana_query.get_single_mut() else {
+        return;
+    };
 
-    for (mut timer, effect, mut animation) in player_cards_query.iter_mut() {
-        timer.0.tick(time.delta());
-        if timer.0.finished() {
-            animation.state = CardAnimationState::MovingUp;
-
-            let mut player_effects = vec![];
-            let mut enemy_effects = vec![];
-
-            match effect {
-                CardEffect::DamageOverTime {
-                    damage,
-                    duration,
-                    frequency,
-                } => {
-                    enemy_effects.push(ActiveEffect::DamageOverTime {
-                        damage: *damage,
-                        duration: Timer::from_seconds(*duration, TimerMode::Once),
-                        frequency: Timer::from_seconds(*frequency, TimerMode::Repeating),
-                    });
-                }
-                CardEffect::DirectDamage(damage) => {
-                    enemy_effects.push(ActiveEffect::DirectDamage(*damage));
-                }
-                CardEffect::Stun { duration } => {
-                    enemy_effects.push(ActiveEffect::Stun {
-                        duration: Timer::from_seconds(*duration, TimerMode::Once),
-                    });
-                }
-                CardEffect::Heal(heal) => {
-                    player_effects.push(ActiveEffect::Heal(*heal));
-                }
+    for (card, mut timer, effect, mut animation, mut sprite, waiting, ready) in
+        player_cards_query.iter_mut()
+    {
+        if !waiting && !ready {
+            timer.0.tick(time.delta());
+            if !timer.0.finished() {
+                continue;
             }
+        }
 
-            for effect in player_effects {
-                let mut player_effects_query = effect_queries.p0();
-                let Ok(mut player_effects) = player_effects_query.get_single_mut() else {
-                    println!("[player_auto_attack] No player effects found");
-                    return;
-                };
-                player_effects.effects.push(effect);
+        // In manual mode a charged card holds until the player clicks it
+        if settings.mode == PlayMode::Manual {
+            if !ready {
+                let duration = timer.0.duration();
+                timer.0.set_elapsed(duration);
+                sprite.color = READY_TO_PLAY_COLOR;
+                commands
+                    .entity(card)
+                    .remove::<WaitingForMana>()
+                    .insert(ReadyToPlay);
             }
+            continue;
+        }
+        if ready {
+            commands.entity(card).remove::<ReadyToPlay>();
+        }
 
-            for effect in enemy_effects {
-                let mut enemy_effects_query = effect_queries.p1();
-                let Ok(mut enemy_effects) = enemy_effects_query.get_single_mut() else {
-                    println!("[player_auto_attack] No enemy found");
-                    return;
-                };
-                enemy_effects.effects.push(effect);
+        // A charged card holds until the player can pay for it
+        let cost = effect.mana_cost();
+        if !mana.can_afford(cost) {
+            if !waiting {
+                let duration = timer.0.duration();
+                timer.0.set_elapsed(duration);
+                sprite.color = WAITING_FOR_MANA_COLOR;
+                commands.entity(card).insert(WaitingForMana);
             }
+            continue;
         }
+        if waiting || ready {
+            timer.0.reset();
+            sprite.color = Color::WHITE;
+            commands.entity(card).remove::<WaitingForMana>();
+        }
+        mana.spend(cost);
+
+        animation.state = CardAnimationState::MovingUp;
+
+        play_card(
+            Combatant::Player,
+            effect,
+            &stats,
+            &mut rng,
+            &mut effects,
+            &mut combat_events,
+        );
     }
 }
 
@@ -791,12 +1134,14 @@ fn check_enemy_death(
     enemy_query: Query<(Entity, &EnemyHealth)>,
     mut next_state: ResMut<NextState<GameState>>,
     mut commands: Commands,
+    mut combat_events: EventWriter<CombatEvent>,
 ) {
     let mut alive_enemies = 0;
     for (entity, enemy_health) in enemy_query.iter() {
         match enemy_health.0.cmp(&0) {
             std::cmp::Ordering::Less | std::cmp::Ordering::Equal => {
                 commands.entity(entity).despawn_recursive();
+                combat_events.send(CombatEvent::EnemyKilled);
             }
             std::cmp::Ordering::Greater => {
                 alive_enemies += 1;
@@ -811,6 +1156,7 @@ fn check_player_death(
     player_query: Query<(Entity, &PlayerHealth)>,
     mut next_state: ResMut<NextState<GameState>>,
     mut commands: Commands,
+    mut combat_events: EventWriter<CombatEvent>,
 ) {
     let mut alive_players = 0;
     for (entity, player_health) in player_query.iter() {
@@ -824,6 +1170,7 @@ fn check_player_death(
         }
     }
     if alive_players == 0 {
+        combat_events.send(CombatEvent::PlayerDied);
         next_state.set(GameState::GameOver);
     }
 }
@@ -837,88 +1184,87 @@ fn despawn_loot_screen(mut commands: Commands, loot_screen_query: Query<Entity,
     }
 }
 
-fn spawn_loot_screen(mut commands: Commands, game_config: Res<GameConfig>) {
-    let loot_items = (0..10)
-        .map(|i| {
-            let rand_rarity = random_range(0..3);
-            let rarity = match rand_rarity {
-                0 => LootRarity::Common,
-                1 => LootRarity::Rare,
-                _ => LootRarity::Epic,
-            };
-            let rand_item = random_range(0..3);
-            let item = match rand_item {
-                0 => "Health Potion",
-                1 => "Magic Sword",
-                _ => "Ancient Relic",
-            };
-            LootItem {
-                name: format!("{}", item),
-                rarity,
-            }
-        })
-        .collect::<Vec<_>>();
+fn spawn_loot_screen(
+    mut commands: Commands,
+    dungeon: Res<Dungeon>,
+    loot_tables: Res<LootTables>,
+    mut loot_pity: ResMut<LootPity>,
+) {
+    let rewards = loot_tables.roll(dungeon.encounter, dungeon.rank, &mut loot_pity);
 
-    // Spawn background overlay
-    let parent = commands
+    commands
         .spawn((
-            Sprite {
-                color: Color::srgb(0.2, 0.1, 0.0),
-                custom_size: Some(Vec2::new(
-                    game_config.screen_width,
-                    game_config.screen_height,
-                )),
-                ..default()
-            },
+            Name::new("Loot Screen"),
+            screen_node(Color::srgb(0.2, 0.1, 0.0)),
             LootScreen,
-            Transform::from_xyz(0.0, 0.0, 0.9),
         ))
-        .id();
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont::from_font_size(14.0),
+                TextColor(Color::WHITE),
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(20.0),
+                    ..default()
+                },
+                LootCapacityText,
+            ));
 
-    // Spawn loot items
-    for (i, loot_item) in loot_items.iter().enumerate() {
-        let y_pos = game_config.screen_height / 4.0 - (i as f32 + 1.0) * 50.0;
+            // Spawn rewards
+            for reward in rewards.iter() {
+                let rarity = reward.rarity();
+                parent
+                    .spawn((
+                        box_node(Vec2::new(200.0, 40.0), rarity.get_color()),
+                        reward.clone(),
+                        Focusable,
+                    ))
+                    .with_child(label_node(reward.label(), 16.0, rarity.get_text_color()))
+                    .observe(take_loot_on::<Pointer<Click>>());
+            }
 
-        commands.entity(parent).with_children(|parent| {
             parent
-                .spawn((
-                    Sprite {
-                        color: loot_item.rarity.get_color(),
-                        custom_size: Some(Vec2::new(200.0, 40.0)),
-                        ..default()
-                    },
-                    Transform::from_xyz(0.0, y_pos, 1.0),
-                    loot_item.clone(),
-                    Interaction::None,
-                ))
+                .spawn(Node {
+                    margin: UiRect::top(Val::Px(40.0)),
+                    column_gap: Val::Px(40.0),
+                    ..default()
+                })
                 .with_children(|parent| {
-                    parent.spawn((
-                        Text2d::new(&loot_item.name),
-                        TextColor(loot_item.rarity.get_text_color()),
-                        Transform::from_xyz(0.0, 0.0, 0.1),
-                    ));
+                    parent
+                        .spawn((
+                            box_node(Vec2::new(120.0, 40.0), Color::srgb(0.3, 0.7, 0.3)),
+                            LootAllButton,
+                            Focusable,
+                        ))
+                        .with_child(label_node("Loot All", 16.0, Color::WHITE))
+                        .observe(handle_loot_all::<Pointer<Click>>());
+
+                    parent
+                        .spawn((
+                            box_node(Vec2::new(120.0, 40.0), Color::srgb(0.4, 0.4, 0.4)),
+                            Focusable,
+                        ))
+                        .with_child(label_node("Continue", 16.0, Color::WHITE))
+                        .observe(leave_loot_screen_on::<Pointer<Click>>());
                 });
-
-            parent
-                .spawn((
-                    Sprite {
-                        color: Color::rgb(0.3, 0.7, 0.3),
-                        custom_size: Some(Vec2::new(120.0, 40.0)),
-                        ..default()
-                    },
-                    Transform::from_xyz(0.0, -game_config.screen_height / 3.0, 1.0),
-                    LootAllButton,
-                    // Interaction::None,
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        Text2d::new("Loot All"),
-                        TextColor(Color::WHITE),
-                        Transform::from_xyz(0.0, 0.0, 0.1),
-                    ));
-                })
-                .observe(handle_loot_all::<Pointer<Click>>());
         });
+}
+
+fn update_loot_capacity_text(
+    inventory: Res<Inventory>,
+    mut text_query: Query<&mut Text, With<LootCapacityText>>,
+) {
+    for mut text in text_query.iter_mut() {
+        let label = format!(
+            "Inventory {}/{}   Gold {}",
+            inventory.items.len(),
+            INVENTORY_CAPACITY,
+            inventory.gold
+        );
+        if text.0 != label {
+            text.0 = label;
+        }
     }
 }
 
@@ -929,113 +1275,267 @@ enum GameMenu {
 }
 
 fn toggle_ui(
-    input: Res<ButtonInput<KeyCode>>,
+    actions: Res<ActionInput>,
     mut next_state: ResMut<NextState<GameState>>,
     current_state: Res<State<GameState>>,
 ) {
-    if input.just_pressed(KeyCode::KeyI) {
-        if current_state.get() == &GameState::Battle {
-            next_state.set(GameState::Menu);
-        } else {
-            next_state.set(GameState::Battle);
+    if actions.just_pressed(Action::OpenInventory) {
+        match current_state.get() {
+            GameState::Battle => next_state.set(GameState::Menu),
+            GameState::Menu => next_state.set(GameState::Battle),
+            _ => {}
         }
     }
 }
 
+fn despawn_menu(mut commands: Commands, menu_query: Query<Entity, With<GameMenu>>) {
+    if let Ok(menu) = menu_query.get_single() {
+        commands.entity(menu).despawn_recursive();
+    }
+}
+
+fn handle_loot_all<E: Debug + Clone + Reflect>() -> impl Fn(
+    Trigger<E>,
+    (
+        Query<&Children, With<LootScreen>>,
+        Query<&Reward>,
+        ResMut<NextState<GameState>>,
+        ResMut<Inventory>,
+        ResMut<Deck>,
+        ResMut<Relics>,
+        ResMut<Dungeon>,
+        Res<Experience>,
+        Commands,
+    ),
+) {
+    move |ev,
+          (
+        loot_screen_query,
+        reward_query,
+        mut next_state,
+        mut inventory,
+        mut deck,
+        mut relics,
+        mut dungeon,
+        experience,
+        mut commands,
+    )| {
+        println!("handle_loot_all");
+        let Ok(loot_screen_children) = loot_screen_query.get_single() else {
+            return;
+        };
+
+        let mut left_behind = 0;
+        for child in loot_screen_children.iter() {
+            let Ok(reward) = reward_query.get(*child) else {
+                continue;
+            };
+            if take_reward(
+                *child,
+                reward,
+                &mut inventory,
+                &mut deck,
+                &mut relics,
+                &mut commands,
+            ) {
+                println!("reward: {}", reward.label());
+            } else {
+                left_behind += 1;
+            }
+        }
+
+        // Anything that didn't fit stays on screen so the player can make room for it
+        if left_behind == 0 {
+            dungeon.advance();
+            next_state.set(experience.next_state_after_loot());
+        }
+    }
+}
+
+/// Claims a reward shown on the loot screen and removes it, unless it needs an inventory slot
+/// and there isn't one free.
+fn take_reward(
+    entity: Entity,
+    reward: &Reward,
+    inventory: &mut Inventory,
+    deck: &mut Deck,
+    relics: &mut Relics,
+    commands: &mut Commands,
+) -> bool {
+    if reward.needs_inventory_slot() && !inventory.has_room() {
+        return false;
+    }
+    reward.clone().claim(inventory, deck, relics);
+    commands.entity(entity).despawn_recursive();
+    true
+}
+
+fn take_loot_on<E: Debug + Clone + Reflect>() -> impl Fn(
+    Trigger<E>,
+    (
+        Query<&Reward>,
+        Query<Entity, With<LootScreen>>,
+        Query<(), With<DiscardPanel>>,
+        ResMut<Inventory>,
+        ResMut<Deck>,
+        ResMut<Relics>,
+        Commands,
+    ),
+) {
+    move |ev,
+          (
+        reward_query,
+        loot_screen_query,
+        discard_panel_query,
+        mut inventory,
+        mut deck,
+        mut relics,
+        mut commands,
+    )| {
+        if !discard_panel_query.is_empty() {
+            return;
+        }
+        let Ok(reward) = reward_query.get(ev.entity()) else {
+            return;
+        };
+        if take_reward(
+            ev.entity(),
+            reward,
+            &mut inventory,
+            &mut deck,
+            &mut relics,
+            &mut commands,
+        ) {
+            return;
+        }
+
+        let Ok(loot_screen) = loot_screen_query.get_single() else {
+            return;
+        };
+        commands.entity(loot_screen).with_children(|parent| {
+            spawn_discard_panel(parent, ev.entity(), &inventory);
+        });
+    }
+}
+
+fn leave_loot_screen_on<E: Debug + Clone + Reflect>() -> impl Fn(
+    Trigger<E>,
+    (
+        ResMut<NextState<GameState>>,
+        ResMut<Dungeon>,
+        Res<Experience>,
+    ),
+) {
+    move |_ev, (mut next_state, mut dungeon, experience)| {
+        dungeon.advance();
+        next_state.set(experience.next_state_after_loot());
+    }
+}
+
 #[derive(Component)]
-struct InventoryScroll {
-    offset: f32,
-    max_offset: f32,
+struct LootCapacityText;
+
+/// Shown when the player tries to take an item with a full inventory. Holds the loot entry
+/// waiting to be picked up once something has been thrown away.
+#[derive(Component)]
+struct DiscardPanel {
+    pending: Entity,
 }
 
-fn spawn_menu(mut commands: Commands, resource: Res<Inventory>, game_config: Res<GameConfig>) {
-    let total_items = resource.items.len() as f32;
-    let max_scroll =
-        (total_items * INVENTORY_ITEM_HEIGHT) - (INVENTORY_VISIBLE_ITEMS * INVENTORY_ITEM_HEIGHT);
-    let max_scroll = max_scroll.max(0.0); // Don't allow negative scroll range
+fn spawn_discard_panel(parent: &mut ChildBuilder, pending: Entity, inventory: &Inventory) {
+    let row_size = Vec2::new(220.0, 24.0);
 
-    // Background panel
-    commands
+    // Covers the loot underneath so it can't be clicked until the panel is closed
+    parent
         .spawn((
-            Sprite {
-                color: Color::srgba(0.0, 0.0, 0.0, 0.9),
-                custom_size: Some(Vec2::new(
-                    game_config.screen_width * 0.4,
-                    INVENTORY_VISIBLE_ITEMS * INVENTORY_ITEM_HEIGHT, // Fixed height based on visible items
-                )),
+            Name::new("Discard Panel"),
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
                 ..default()
             },
-            Transform::from_xyz(0.0, 0.0, 0.9),
-            InventoryDisplay,
-            InventoryScroll {
-                offset: 0.0,
-                max_offset: max_scroll,
-            },
-            Visibility::default(),
-            GameMenu::Inventory,
+            GlobalZIndex(1),
+            DiscardPanel { pending },
         ))
         .with_children(|parent| {
-            // Title (above the scroll area)
-            parent.spawn((
-                Text2d::new("Inventory"),
-                TextColor(Color::WHITE),
-                Transform::from_xyz(
-                    0.0,
-                    INVENTORY_VISIBLE_ITEMS * INVENTORY_ITEM_HEIGHT / 2.0 + 20.0,
-                    0.1,
-                ),
-            ));
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(260.0),
+                        padding: UiRect::all(Val::Px(10.0)),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(4.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.95)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(label_node(
+                        "Inventory full - discard an item",
+                        14.0,
+                        Color::WHITE,
+                    ));
 
-            // Items container
-            for (i, item) in resource.items.iter().enumerate() {
-                let y_offset = -((i as f32) * INVENTORY_ITEM_HEIGHT);
+                    for (i, item) in inventory.items.iter().enumerate() {
+                        parent
+                            .spawn((box_node(row_size, item.rarity.get_color()), Focusable))
+                            .with_child(label_node(&item.name, 12.0, item.rarity.get_text_color()))
+                            .observe(discard_item_on::<Pointer<Click>>(i));
+                    }
 
-                parent
-                    .spawn((
-                        Sprite {
-                            color: item.rarity.get_color(),
-                            custom_size: Some(Vec2::new(
-                                game_config.screen_width * 0.35,
-                                INVENTORY_ITEM_HEIGHT - 10.0, // Leave some spacing
-                            )),
-                            ..default()
-                        },
-                        Transform::from_xyz(0.0, y_offset, 0.1),
-                    ))
-                    .with_children(|item_parent| {
-                        item_parent.spawn((
-                            Text2d::new(&item.name),
-                            TextColor(item.rarity.get_text_color()),
-                            Transform::from_xyz(-game_config.screen_width * 0.15, 0.0, 0.1),
-                        ));
-                    });
-            }
+                    parent
+                        .spawn((box_node(row_size, Color::srgb(0.4, 0.4, 0.4)), Focusable))
+                        .with_child(label_node("Cancel", 12.0, Color::WHITE))
+                        .observe(cancel_discard_on::<Pointer<Click>>());
+                });
         });
 }
 
-fn despawn_menu(mut commands: Commands, menu_query: Query<Entity, With<GameMenu>>) {
-    if let Ok(menu) = menu_query.get_single() {
-        commands.entity(menu).despawn_recursive();
-    }
-}
-
-fn handle_loot_all<E: Debug + Clone + Reflect>() -> impl Fn(
+fn discard_item_on<E: Debug + Clone + Reflect>(
+    index: usize,
+) -> impl Fn(
     Trigger<E>,
     (
-        Query<&LootItem>,
-        ResMut<NextState<GameState>>,
+        Query<(Entity, &DiscardPanel)>,
+        Query<&Reward>,
         ResMut<Inventory>,
+        ResMut<Deck>,
+        ResMut<Relics>,
         Commands,
     ),
 ) {
-    move |ev, (loot_query, mut next_state, mut inventory, mut commands)| {
-        println!("handle_loot_all");
-        for loot_item in loot_query.iter() {
-            println!("loot_item: {}", loot_item.name);
-            inventory.items.push(loot_item.clone());
+    move |_ev, (panel_query, reward_query, mut inventory, mut deck, mut relics, mut commands)| {
+        let Ok((panel, discard_panel)) = panel_query.get_single() else {
+            return;
+        };
+        if index < inventory.items.len() {
+            let discarded = inventory.items.remove(index);
+            println!("[discard_item_on] Discarded {}", discarded.name);
+        }
+        if let Ok(reward) = reward_query.get(discard_panel.pending) {
+            take_reward(
+                discard_panel.pending,
+                reward,
+                &mut inventory,
+                &mut deck,
+                &mut relics,
+                &mut commands,
+            );
         }
+        commands.entity(panel).despawn_recursive();
+    }
+}
 
-        next_state.set(GameState::Battle);
+fn cancel_discard_on<E: Debug + Clone + Reflect>(
+) -> impl Fn(Trigger<E>, (Query<Entity, With<DiscardPanel>>, Commands)) {
+    move |_ev, (panel_query, mut commands)| {
+        for panel in panel_query.iter() {
+            commands.entity(panel).despawn_recursive();
+        }
     }
 }
 
@@ -1065,7 +1565,10 @@ fn update_skill_timer_bars(
 }
 
 fn update_card_timers(
-    mut card_query: Query<(&mut CardAttackTimer, &Parent)>,
+    mut card_query: Query<
+        (&mut CardAttackTimer, &Parent),
+        (Without<WaitingForMana>, Without<ReadyToPlay>),
+    >,
     player_query: Query<(Entity, &Effects), With<PlayerEntity>>,
     enemy_query: Query<(Entity, &Effects), With<EnemyEntity>>,
     time: Res<Time>,
@@ -1117,208 +1620,106 @@ fn despawn_battle_entities(
     }
 }
 
-fn handle_inventory_scroll(
-    mut scroll_query: Query<(&mut InventoryScroll, &Children)>,
-    mut item_query: Query<&mut Transform>,
-    input: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
-) {
-    for (mut scroll, children) in scroll_query.iter_mut() {
-        let mut scroll_direction = 0.0;
-
-        if input.pressed(KeyCode::ArrowUp) {
-            scroll_direction += 1.0;
-        }
-        if input.pressed(KeyCode::ArrowDown) {
-            scroll_direction -= 1.0;
-        }
-
-        // println!("scroll_direction: {}", scroll_direction);
-        if scroll_direction != 0.0 {
-            // Update scroll offset
-            scroll.offset += scroll_direction * SCROLL_SPEED * time.delta().as_secs_f32();
-            // println!("scroll.offset: {}", scroll.offset);
-            scroll.offset = scroll.offset.clamp(0.0, scroll.max_offset);
-
-            // Update item positions
-            for child in children.iter() {
-                if let Ok(mut transform) = item_query.get_mut(*child) {
-                    let original_y = transform.translation.y;
-                    if scroll_direction > 0.0 {
-                        transform.translation.y = original_y + scroll.offset;
-                    } else {
-                        transform.translation.y = original_y - scroll.offset;
-                    }
-                }
-            }
-        }
-    }
-}
-
-#[derive(Component)]
-struct StunIndicator;
-
-fn update_stun_indicators(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    effect_query: Query<
-        (Entity, &Effects, Option<&Children>),
-        Or<(With<PlayerEntity>, With<EnemyEntity>)>,
-    >,
-    stun_indicators: Query<Entity, With<StunIndicator>>,
-) {
-    for (entity, effects, children) in effect_query.iter() {
-        let is_stunned = effects.effects.iter().any(
-            |effect| matches!(effect, ActiveEffect::Stun { duration } if !duration.finished()),
-        );
-
-        // Check if entity already has a stun indicator
-        let has_indicator = children
-            .map(|children| {
-                children
-                    .iter()
-                    .any(|child| stun_indicators.contains(*child))
-            })
-            .unwrap_or(false);
-
-        if is_stunned && !has_indicator {
-            // Add stun indicator
-            commands.entity(entity).with_children(|parent| {
-                parent.spawn((
-                    Name::new("Stun Indicator"),
-                    Sprite {
-                        image: asset_server.load("stun.png"),
-                        custom_size: Some(Vec2::new(32.0, 32.0)),
-                        ..default()
-                    },
-                    Transform::from_xyz(0.0, 50.0, 0.2), // Position above the entity
-                    StunIndicator,
-                ));
-            });
-        } else if !is_stunned && has_indicator {
-            // Remove stun indicator
-            if let Some(children) = children {
-                for child in children.iter() {
-                    if stun_indicators.contains(*child) {
-                        commands.entity(*child).despawn_recursive();
-                    }
-                }
-            }
-        }
-    }
-}
-
-fn show_game_over(mut commands: Commands, game_config: Res<GameConfig>) {
+fn show_game_over(mut commands: Commands, history: Res<RunHistory>) {
     // show end game screen covering whole screen
     commands
         .spawn((
             Name::new("Game Over Screen"),
-            Sprite {
-                color: Color::srgba(0.3, 0.2, 0.0, 1.0),
-                custom_size: Some(Vec2::new(
-                    game_config.screen_width,
-                    game_config.screen_height,
-                )),
-                ..default()
-            },
-            Transform::from_xyz(0.0, 0.0, MENU_Z_LAYER),
+            screen_node(Color::srgba(0.3, 0.2, 0.0, 1.0)),
             MenuItem,
         ))
         .with_children(|parent| {
+            parent.spawn((
+                Text::new("Game Over"),
+                TextFont::from_font_size(32.0),
+                TextColor(Color::WHITE),
+            ));
+            spawn_run_summary(parent, &history);
             parent
                 .spawn((
-                    Sprite {
-                        color: Color::srgb(0.3, 0.2, 0.8),
-                        custom_size: Some(Vec2::new(100.0, 100.0)),
-                        ..default()
-                    },
-                    Transform::from_xyz(0.0, 0.0, MENU_Z_LAYER + 0.1),
+                    box_node(Vec2::new(140.0, 40.0), Color::srgb(0.3, 0.2, 0.8)),
+                    Focusable,
                 ))
-                .with_child((
-                    Text2d::new("New run"),
-                    TextColor(Color::WHITE),
-                    Transform::from_xyz(0.0, 0.0, MENU_Z_LAYER + 0.2),
-                ))
-                .observe(recolor_on::<Pointer<Over>>(Color::srgb(0.8, 0.8, 0.8)))
-                .observe(recolor_on::<Pointer<Out>>(Color::srgb(0.3, 0.2, 0.8)))
-                .observe(translate_on::<Pointer<Down>>(Vec2::new(10.0, -10.0)))
-                .observe(translate_on::<Pointer<Up>>(Vec2::new(-10.0, 10.0)))
-                .observe(respawn_on::<Pointer<Click>>(Vec2::new(-10.0, 10.0)));
+                .with_child(label_node("New run", 16.0, Color::WHITE))
+                .observe(recolor_node_on::<Pointer<Over>>(Color::srgb(0.8, 0.8, 0.8)))
+                .observe(recolor_node_on::<Pointer<Out>>(Color::srgb(0.3, 0.2, 0.8)))
+                .observe(new_run_on::<Pointer<Click>>());
         });
 }
 
 #[derive(Component)]
 struct MenuItem;
 
-fn respawn_on<E: Debug + Clone + Reflect>(
-    direction: Vec2,
-) -> impl Fn(
-    Trigger<E>,
-    (
-        Commands,
-        ResMut<NextState<GameState>>,
-        Query<&mut PlayerHealth>,
-        Query<Entity, With<MenuItem>>,
-    ),
-) {
-    println!("respawn_on");
-    move |ev, (mut commands, mut next_state, mut player_health, mut menu_entity)| {
-        println!("respawn_on end");
-        // commands.entity(ev.entity()).despawn_recursive();
-        if let Ok(mut player_health) = player_health.get_mut(ev.entity()) {
-            player_health.0 = 100;
-        }
-        next_state.set(GameState::Battle);
-        if let Ok(menu_entity) = menu_entity.get_single() {
-            commands.entity(menu_entity).despawn_recursive();
-        }
+fn despawn_game_over(mut commands: Commands, menu_query: Query<Entity, With<MenuItem>>) {
+    for menu in menu_query.iter() {
+        commands.entity(menu).despawn_recursive();
     }
 }
 
-fn translate_on<E: Debug + Clone + Reflect>(
-    direction: Vec2,
-) -> impl Fn(Trigger<E>, (Query<&mut Transform>)) {
-    // println!("respawn_on");
-    move |ev, mut transforms| {
-        let Ok(mut transform) = transforms.get_mut(ev.entity()) else {
-            return;
-        };
-        transform.translation += Vec3::new(direction.x, direction.y, 0.0);
-        // println!("respawn_on end");
+/// The next run starts over from class selection; `start_new_run` clears out this one.
+fn new_run_on<E: Debug + Clone + Reflect>() -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+    move |_ev, mut next_state| {
+        next_state.set(GameState::ClassSelect);
     }
 }
 
-fn recolor_on<E: Debug + Clone + Reflect>(
+fn recolor_node_on<E: Debug + Clone + Reflect>(
     color: Color,
-) -> impl Fn(Trigger<E>, (Query<&mut Sprite>)) {
-    move |ev, mut sprites| {
-        let Ok(mut sprite) = sprites.get_mut(ev.entity()) else {
+) -> impl Fn(Trigger<E>, Query<&mut BackgroundColor>) {
+    move |ev, mut backgrounds| {
+        let Ok(mut background) = backgrounds.get_mut(ev.entity()) else {
             return;
         };
-        sprite.color = color;
+        background.0 = color;
     }
 }
 fn main() {
+    let settings = Settings::load();
     App::new()
         .add_plugins((
             DefaultPlugins
                 .set(ImagePlugin::default_nearest())
                 .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: "Bee Game".to_string(),
-                        resolution: WindowResolution::new(640.0, 480.0),
-                        ..default()
-                    }),
+                    primary_window: Some(settings.window()),
                     ..default()
                 }),
-            WorldInspectorPlugin::new(),
+            WorldInspectorPlugin::new().run_if(inspector_enabled),
         ))
         // .add_plugins(InspectorPlugin::<GameState>::new())
         // .add_plugins(InspectorPlugin::<GameConfig>::new())
         // .add_plugins(WorldInspectorPlugin::new())
         .init_state::<GameState>()
+        .init_state::<PauseState>()
         .init_resource::<Inventory>()
-        .add_systems(Startup, (setup))
+        .init_resource::<InventoryView>()
+        .init_resource::<Relics>()
+        .init_resource::<Dungeon>()
+        .init_resource::<LootTables>()
+        .init_resource::<LootPity>()
+        .init_resource::<NarrativeEvents>()
+        .init_resource::<Experience>()
+        .init_resource::<RunRng>()
+        .init_resource::<CombatLog>()
+        .init_resource::<CardPlaySettings>()
+        .init_resource::<RunStatus>()
+        .init_resource::<ActionInput>()
+        .init_resource::<Rebinding>()
+        .init_resource::<Focus>()
+        .insert_resource(InputBindings::load())
+        .init_resource::<RunStats>()
+        .insert_resource(RunHistory::load())
+        .init_resource::<PendingTarget>()
+        .init_resource::<Deck>()
+        .add_event::<CombatEvent>()
+        .add_systems(
+            Startup,
+            (
+                setup,
+                spawn_level_text,
+                spawn_play_mode_text,
+                spawn_focus_cursor,
+            ),
+        )
         .add_systems(
             Update,
             (
@@ -1333,35 +1734,178 @@ fn main() {
                 animate_cards,
                 check_enemy_death,
                 check_player_death,
-                update_stun_indicators,
+                update_health_bars,
+                update_status_icons,
+            )
+                .chain()
+                .run_if(in_state(GameState::Battle))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(
+            Update,
+            (
+                quit_game,
+                toggle_ui.run_if(in_state(PauseState::Running)),
+                toggle_pause.run_if(in_state(GameState::Battle)),
+                update_gold_text,
+                apply_stamina,
+                toggle_play_mode,
+            ),
+        )
+        .add_systems(
+            Update,
+            (
+                trigger_relics,
+                award_experience,
+                update_level_text,
+                refill_mana,
+            ),
+        )
+        .add_systems(Update, (show_combat_text, animate_floating_text))
+        .add_systems(
+            Update,
+            (
+                record_combat_log,
+                handle_combat_log_input,
+                update_combat_log_panel,
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            (
+                handle_potion_hotkeys,
+                update_potion_belt,
+                regenerate_mana,
+                update_mana_bar,
+                play_card_hotkeys,
+                cancel_pending_target,
+                update_target_highlight,
             )
                 .chain()
-                .run_if(in_state(GameState::Battle)),
+                .run_if(in_state(GameState::Battle))
+                .run_if(in_state(PauseState::Running)),
+        )
+        .add_systems(OnEnter(GameState::Title), spawn_title_screen)
+        .add_systems(OnExit(GameState::Title), despawn_title_screen)
+        .add_systems(
+            Update,
+            leave_title_screen.run_if(in_state(GameState::Title)),
+        )
+        .add_systems(OnEnter(GameState::MainMenu), spawn_main_menu)
+        .add_systems(OnExit(GameState::MainMenu), despawn_main_menu)
+        .add_systems(OnEnter(GameState::Profile), spawn_profile_screen)
+        .add_systems(OnExit(GameState::Profile), despawn_profile_screen)
+        .add_systems(OnEnter(GameState::Settings), spawn_settings_screen)
+        .add_systems(OnExit(GameState::Settings), despawn_settings_screen)
+        .add_systems(
+            Update,
+            refresh_settings_screen.run_if(in_state(GameState::Settings)),
+        )
+        .add_systems(Update, apply_settings)
+        .add_systems(PreUpdate, track_window_size)
+        .add_systems(
+            PostUpdate,
+            (apply_screen_anchors, fit_full_screen).before(TransformSystem::TransformPropagate),
+        )
+        .add_systems(OnEnter(GameState::Controls), spawn_controls_screen)
+        .add_systems(OnExit(GameState::Controls), despawn_controls_screen)
+        .add_systems(
+            Update,
+            (capture_rebinding, refresh_controls_screen)
+                .chain()
+                .run_if(in_state(GameState::Controls)),
+        )
+        .add_systems(PreUpdate, read_actions.after(InputSystem))
+        .add_systems(
+            Update,
+            (navigate_focus, confirm_focus, update_focus_cursor).chain(),
+        )
+        .add_systems(OnEnter(PauseState::Paused), spawn_pause_menu)
+        .add_systems(OnExit(PauseState::Paused), despawn_pause_menu)
+        .add_systems(
+            OnEnter(GameState::Battle),
+            (
+                start_new_run.before(on_enter_battle),
+                on_enter_battle,
+                spawn_potion_belt,
+                spawn_combat_log,
+            ),
+        )
+        .add_systems(
+            OnExit(GameState::Battle),
+            (
+                despawn_potion_belt,
+                despawn_combat_log,
+                despawn_card_tooltips,
+            ),
         )
-        .add_systems(Update, (debug_display_state, toggle_ui))
-        .add_systems(OnEnter(GameState::Battle), on_enter_battle)
         .add_systems(
             OnEnter(GameState::LootScreen),
             (despawn_battle_entities, spawn_loot_screen).chain(),
         )
         // .add_systems(OnEnter(GameState::EndBattle), despawn_battle_entities)
         .add_systems(OnExit(GameState::LootScreen), despawn_loot_screen)
-        .add_systems(OnEnter(GameState::Menu), spawn_menu)
+        .add_systems(
+            Update,
+            update_loot_capacity_text.run_if(in_state(GameState::LootScreen)),
+        )
+        .add_systems(OnEnter(GameState::LevelUp), spawn_level_up_screen)
+        .add_systems(OnExit(GameState::LevelUp), despawn_level_up_screen)
+        .add_systems(
+            Update,
+            refresh_level_up_screen.run_if(in_state(GameState::LevelUp)),
+        )
+        .add_systems(OnEnter(GameState::CardReward), spawn_card_reward)
+        .add_systems(OnExit(GameState::CardReward), despawn_card_reward)
+        .add_systems(OnEnter(GameState::Shop), open_shop)
+        .add_systems(OnExit(GameState::Shop), close_shop)
+        .add_systems(
+            Update,
+            refresh_shop_screen.run_if(in_state(GameState::Shop)),
+        )
+        .add_systems(OnEnter(GameState::RestSite), spawn_rest_site)
+        .add_systems(OnExit(GameState::RestSite), despawn_rest_site)
+        .add_systems(
+            Update,
+            refresh_rest_site.run_if(in_state(GameState::RestSite)),
+        )
+        .add_systems(OnEnter(GameState::Event), spawn_event_screen)
+        .add_systems(OnExit(GameState::Event), despawn_event_screen)
+        .add_systems(
+            Update,
+            refresh_event_screen.run_if(in_state(GameState::Event)),
+        )
+        .add_systems(OnEnter(GameState::Menu), spawn_inventory_screen)
         .add_systems(OnExit(GameState::Menu), despawn_menu)
         .add_systems(
             Update,
-            handle_inventory_scroll.run_if(in_state(GameState::Menu)),
+            (handle_inventory_scroll, refresh_inventory_screen)
+                .chain()
+                .run_if(in_state(GameState::Menu)),
         )
-        .add_systems(OnEnter(GameState::GameOver), show_game_over)
-        .insert_resource(GameConfig {
-            screen_width: 640.0,
-            screen_height: 480.0,
-        })
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            (finish_run, show_game_over).chain(),
+        )
+        .add_systems(OnExit(GameState::GameOver), despawn_game_over)
+        .add_systems(OnEnter(GameState::ClassSelect), spawn_class_select)
+        .add_systems(OnExit(GameState::ClassSelect), despawn_class_select)
+        .add_systems(PostUpdate, record_run_stats)
+        .add_systems(
+            Update,
+            tick_play_time.run_if(in_run.and(in_state(PauseState::Running))),
+        )
+        .insert_resource(settings.game_config())
+        .insert_resource(settings)
         .register_type::<Effects>()
         .register_type::<ActiveEffect>()
         .register_type::<PlayerHealth>()
         .register_type::<EnemyHealth>()
         .register_type::<PlayerEntity>()
         .register_type::<EnemyEntity>()
+        .register_type::<EnemyKind>()
+        .register_type::<Mana>()
+        .register_type::<Shield>()
         .run();
 }
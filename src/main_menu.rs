@@ -0,0 +1,267 @@
+pub mod main_menu {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+
+    use crate::focus::focus::Focusable;
+    use crate::input::input::{Action, ActionInput};
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::{recolor_node_on, GameState};
+
+    const BUTTON_SIZE: Vec2 = Vec2::new(180.0, 36.0);
+    const BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.4);
+    const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.35, 0.6);
+    const DISABLED_BUTTON_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
+
+    /// Runs on top of whatever game state is active; pausing freezes virtual time, which
+    /// stops every battle timer with it.
+    #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+    pub enum PauseState {
+        #[default]
+        Running,
+        Paused,
+    }
+
+    #[derive(Resource, Default)]
+    pub struct RunStatus {
+        /// Whether a run has been started, so there is something to continue.
+        pub started: bool,
+    }
+
+    #[derive(Component)]
+    pub struct TitleScreen;
+
+    #[derive(Component)]
+    pub struct MainMenuScreen;
+
+    #[derive(Component)]
+    pub struct PauseMenuScreen;
+
+    fn spawn_screen<'a>(
+        commands: &'a mut Commands,
+        name: &'static str,
+        color: Color,
+        marker: impl Bundle,
+    ) -> EntityCommands<'a> {
+        commands.spawn((Name::new(name), screen_node(color), marker))
+    }
+
+    /// A menu button; a disabled one is greyed out and ignores clicks.
+    fn spawn_button<'a>(
+        parent: &'a mut ChildBuilder,
+        label: &str,
+        enabled: bool,
+    ) -> EntityCommands<'a> {
+        let color = if enabled {
+            BUTTON_COLOR
+        } else {
+            DISABLED_BUTTON_COLOR
+        };
+        let mut button = parent.spawn(box_node(BUTTON_SIZE, color));
+        button.with_child(label_node(
+            label,
+            16.0,
+            if enabled {
+                Color::WHITE
+            } else {
+                Color::srgb(0.5, 0.5, 0.5)
+            },
+        ));
+        if enabled {
+            button
+                .insert(Focusable)
+                .observe(recolor_node_on::<Pointer<Over>>(BUTTON_HOVER_COLOR))
+                .observe(recolor_node_on::<Pointer<Out>>(BUTTON_COLOR));
+        }
+        button
+    }
+
+    pub fn spawn_title_screen(mut commands: Commands) {
+        spawn_screen(
+            &mut commands,
+            "Title Screen",
+            Color::srgb(0.05, 0.05, 0.1),
+            TitleScreen,
+        )
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Bee Game"),
+                TextFont::from_font_size(48.0),
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(60.0)),
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new("Press any key"),
+                TextFont::from_font_size(16.0),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+            ));
+        });
+    }
+
+    pub fn despawn_title_screen(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<TitleScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    pub fn leave_title_screen(
+        keys: Res<ButtonInput<KeyCode>>,
+        mouse: Res<ButtonInput<MouseButton>>,
+        gamepads: Query<&Gamepad>,
+        mut next_state: ResMut<NextState<GameState>>,
+    ) {
+        if keys.get_just_pressed().next().is_some()
+            || mouse.get_just_pressed().next().is_some()
+            || gamepads
+                .iter()
+                .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+        {
+            next_state.set(GameState::MainMenu);
+        }
+    }
+
+    /// New Run always goes to class selection; Continue is only offered while a run is going.
+    pub fn spawn_main_menu(mut commands: Commands, run_status: Res<RunStatus>) {
+        spawn_screen(
+            &mut commands,
+            "Main Menu",
+            Color::srgb(0.05, 0.05, 0.1),
+            MainMenuScreen,
+        )
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Bee Game"),
+                TextFont::from_font_size(32.0),
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                Node {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+            let started = run_status.started;
+            spawn_button(parent, "New Run", true).observe(new_run_on::<Pointer<Click>>());
+            let mut continue_run = spawn_button(parent, "Continue", started);
+            if started {
+                continue_run.observe(continue_run_on::<Pointer<Click>>());
+            }
+            spawn_button(parent, "Profile", true).observe(open_profile_on::<Pointer<Click>>());
+            spawn_button(parent, "Settings", true).observe(open_settings_on::<Pointer<Click>>());
+            spawn_button(parent, "Quit", true).observe(quit_on::<Pointer<Click>>());
+        });
+    }
+
+    pub fn despawn_main_menu(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<MainMenuScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    fn new_run_on<E: Debug + Clone + Reflect>() -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>)
+    {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::ClassSelect);
+        }
+    }
+
+    /// Picks the run back up from the battle it was left in.
+    fn continue_run_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::Battle);
+        }
+    }
+
+    fn open_profile_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::Profile);
+        }
+    }
+
+    fn open_settings_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::Settings);
+        }
+    }
+
+    fn quit_on<E: Debug + Clone + Reflect>() -> impl Fn(Trigger<E>, EventWriter<AppExit>) {
+        move |_ev, mut exit| {
+            exit.send(AppExit::Success);
+        }
+    }
+
+    pub fn toggle_pause(
+        actions: Res<ActionInput>,
+        pause_state: Res<State<PauseState>>,
+        mut next_pause_state: ResMut<NextState<PauseState>>,
+    ) {
+        if actions.just_pressed(Action::Pause) {
+            next_pause_state.set(match pause_state.get() {
+                PauseState::Running => PauseState::Paused,
+                PauseState::Paused => PauseState::Running,
+            });
+        }
+    }
+
+    pub fn spawn_pause_menu(mut commands: Commands, mut time: ResMut<Time<Virtual>>) {
+        time.pause();
+        spawn_screen(
+            &mut commands,
+            "Pause Menu",
+            Color::srgba(0.0, 0.0, 0.0, 0.7),
+            PauseMenuScreen,
+        )
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Paused"),
+                TextFont::from_font_size(32.0),
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+            spawn_button(parent, "Resume", true).observe(resume_on::<Pointer<Click>>());
+            spawn_button(parent, "Main Menu", true)
+                .observe(leave_to_main_menu_on::<Pointer<Click>>());
+            spawn_button(parent, "Quit", true).observe(quit_on::<Pointer<Click>>());
+        });
+    }
+
+    pub fn despawn_pause_menu(
+        mut commands: Commands,
+        mut time: ResMut<Time<Virtual>>,
+        screen_query: Query<Entity, With<PauseMenuScreen>>,
+    ) {
+        time.unpause();
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    fn resume_on<E: Debug + Clone + Reflect>() -> impl Fn(Trigger<E>, ResMut<NextState<PauseState>>)
+    {
+        move |_ev, mut next_pause_state| {
+            next_pause_state.set(PauseState::Running);
+        }
+    }
+
+    /// The battle is left as it is, so Continue on the main menu picks it back up.
+    fn leave_to_main_menu_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<PauseState>>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_pause_state, mut next_state| {
+            next_pause_state.set(PauseState::Running);
+            next_state.set(GameState::MainMenu);
+        }
+    }
+}
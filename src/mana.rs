@@ -0,0 +1,114 @@
+pub mod mana {
+    use bevy::prelude::*;
+
+    use crate::combat::combat::CombatEvent;
+    use crate::skills::skills::Stats;
+    use crate::PlayerEntity;
+
+    const MANA_PER_INTELLIGENCE: f32 = 5.0;
+    /// Mana regained per second for each point of intelligence.
+    const REGEN_PER_INTELLIGENCE: f32 = 0.2;
+    const MANA_BAR_WIDTH: f32 = 60.0;
+    const MANA_BAR_HEIGHT: f32 = 6.0;
+    const MANA_COLOR: Color = Color::srgb(0.2, 0.4, 1.0);
+
+    #[derive(Component, Reflect)]
+    pub struct Mana {
+        pub current: f32,
+        pub max: f32,
+    }
+
+    impl Mana {
+        pub fn from_stats(stats: &Stats) -> Mana {
+            let max = stats.intelligence as f32 * MANA_PER_INTELLIGENCE;
+            Mana { current: max, max }
+        }
+
+        pub fn can_afford(&self, cost: u32) -> bool {
+            self.current >= cost as f32
+        }
+
+        pub fn spend(&mut self, cost: u32) {
+            self.current = (self.current - cost as f32).max(0.0);
+        }
+    }
+
+    /// Marks a charged card that is holding its play until there is enough mana for it.
+    #[derive(Component)]
+    pub struct WaitingForMana;
+
+    #[derive(Component)]
+    pub struct ManaBar;
+
+    pub fn add_mana_bar(parent: &mut ChildBuilder, y: f32) {
+        parent.spawn((
+            Name::new("Mana Bar Background"),
+            Sprite {
+                color: Color::srgb(0.15, 0.15, 0.25),
+                custom_size: Some(Vec2::new(MANA_BAR_WIDTH, MANA_BAR_HEIGHT)),
+                ..default()
+            },
+            Transform::from_xyz(0.0, y, 0.1),
+        ));
+        parent.spawn((
+            Name::new("Mana Bar"),
+            Sprite {
+                color: MANA_COLOR,
+                custom_size: Some(Vec2::new(MANA_BAR_WIDTH, MANA_BAR_HEIGHT)),
+                ..default()
+            },
+            Transform::from_xyz(0.0, y, 0.2),
+            ManaBar,
+        ));
+    }
+
+    /// Keeps the pool in line with intelligence, which can change through training and
+    /// level-ups, and tops it up over time.
+    pub fn regenerate_mana(
+        time: Res<Time>,
+        mut mana_query: Query<(&mut Mana, &Stats), With<PlayerEntity>>,
+    ) {
+        for (mut mana, stats) in mana_query.iter_mut() {
+            mana.max = stats.intelligence as f32 * MANA_PER_INTELLIGENCE;
+            let regen = stats.intelligence as f32 * REGEN_PER_INTELLIGENCE * time.delta_secs();
+            mana.current = (mana.current + regen).min(mana.max);
+        }
+    }
+
+    /// Every fight starts with a full pool. Keyed off `BattleStarted` rather than entering the
+    /// battle state, so opening the inventory or pausing mid-fight doesn't top it up.
+    pub fn refill_mana(
+        mut combat_events: EventReader<CombatEvent>,
+        mut mana_query: Query<&mut Mana, With<PlayerEntity>>,
+    ) {
+        if !combat_events
+            .read()
+            .any(|event| matches!(event, CombatEvent::BattleStarted))
+        {
+            return;
+        }
+        for mut mana in mana_query.iter_mut() {
+            mana.current = mana.max;
+        }
+    }
+
+    pub fn update_mana_bar(
+        mana_query: Query<(&Mana, &Children)>,
+        mut mana_bar_query: Query<(&mut Transform, &mut Sprite), With<ManaBar>>,
+    ) {
+        for (mana, children) in mana_query.iter() {
+            for child in children.iter() {
+                if let Ok((mut transform, mut sprite)) = mana_bar_query.get_mut(*child) {
+                    let progress = if mana.max > 0.0 {
+                        mana.current / mana.max
+                    } else {
+                        0.0
+                    };
+                    let width = MANA_BAR_WIDTH * progress;
+                    sprite.custom_size = Some(Vec2::new(width, MANA_BAR_HEIGHT));
+                    transform.translation.x = -MANA_BAR_WIDTH / 2.0 + width / 2.0;
+                }
+            }
+        }
+    }
+}
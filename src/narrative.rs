@@ -0,0 +1,374 @@
+pub mod narrative {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+    use rand::random_range;
+    use serde::Deserialize;
+
+    use crate::card::card::{CardEffect, Deck, Effects};
+    use crate::focus::focus::Focusable;
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::relics::relics::{Relic, Relics};
+    use crate::skills::skills::{Class, StatKind, Stats};
+    use crate::{
+        recolor_node_on, GameState, Inventory, LootItem, LootRarity, MaxHealth, PlayerEntity,
+        PlayerHealth,
+    };
+
+    const TEXT_WIDTH: f32 = 480.0;
+    const CHOICE_SIZE: Vec2 = Vec2::new(420.0, 32.0);
+    const CHOICE_GAP: f32 = 8.0;
+    const CHOICE_COLOR: Color = Color::srgb(0.3, 0.25, 0.35);
+    const CHOICE_HOVER_COLOR: Color = Color::srgb(0.45, 0.35, 0.5);
+    const DISABLED_COLOR: Color = Color::srgba(0.3, 0.3, 0.3, 0.7);
+
+    /// Roll a d10 and add the stat; meeting the difficulty passes.
+    #[derive(Deserialize, Clone, Copy)]
+    struct StatCheck {
+        stat: StatKind,
+        difficulty: usize,
+    }
+
+    #[derive(Deserialize, Clone, Debug)]
+    #[serde(tag = "type")]
+    enum Outcome {
+        Gold {
+            amount: i32,
+        },
+        /// Negative amounts hurt.
+        Health {
+            amount: i32,
+        },
+        GainCard,
+        LoseCard,
+        GainItem {
+            name: String,
+            rarity: LootRarity,
+        },
+        /// Loses the named item, or a random one when no name is given.
+        LoseItem {
+            name: Option<String>,
+        },
+        GainRelic,
+        /// Lingers on the player into the next fight.
+        Status {
+            effect: CardEffect,
+        },
+    }
+
+    #[derive(Deserialize)]
+    struct EventChoice {
+        label: String,
+        check: Option<StatCheck>,
+        outcomes: Vec<Outcome>,
+        result: String,
+        #[serde(default)]
+        failure: Vec<Outcome>,
+        #[serde(default)]
+        failure_result: String,
+    }
+
+    #[derive(Deserialize)]
+    struct NarrativeEvent {
+        title: String,
+        text: String,
+        choices: Vec<EventChoice>,
+    }
+
+    /// Events loaded from `assets/events.json`.
+    #[derive(Resource, Deserialize)]
+    pub struct NarrativeEvents {
+        events: Vec<NarrativeEvent>,
+    }
+
+    impl Default for NarrativeEvents {
+        fn default() -> Self {
+            serde_json::from_str(include_str!("../assets/events.json"))
+                .expect("assets/events.json should be a valid event list")
+        }
+    }
+
+    /// The event the player is currently looking at and, once they have chosen, what happened.
+    #[derive(Resource)]
+    pub struct CurrentEvent {
+        index: usize,
+        result: Option<String>,
+    }
+
+    #[derive(Component)]
+    pub struct EventScreen;
+
+    #[derive(Component)]
+    pub struct EventContent;
+
+    impl EventChoice {
+        /// Choices that give away a specific item need the player to actually have it.
+        fn is_available(&self, inventory: &Inventory) -> bool {
+            self.outcomes.iter().all(|outcome| match outcome {
+                Outcome::LoseItem { name: Some(name) } => {
+                    inventory.items.iter().any(|item| &item.name == name)
+                }
+                _ => true,
+            })
+        }
+
+        fn label(&self) -> String {
+            match self.check {
+                Some(check) => format!(
+                    "[{} {}] {}",
+                    check.stat.name(),
+                    check.difficulty,
+                    self.label
+                ),
+                None => self.label.clone(),
+            }
+        }
+    }
+
+    impl Outcome {
+        fn describe(&self) -> String {
+            match self {
+                Outcome::Gold { amount } if *amount >= 0 => format!("+{} gold", amount),
+                Outcome::Gold { amount } => format!("{} gold", amount),
+                Outcome::Health { amount } if *amount >= 0 => format!("+{} HP", amount),
+                Outcome::Health { amount } => format!("{} HP", amount),
+                Outcome::GainCard => "Gained a card".to_string(),
+                Outcome::LoseCard => "Lost a card".to_string(),
+                Outcome::GainItem { name, .. } => format!("Gained {}", name),
+                Outcome::LoseItem { name: Some(name) } => format!("Lost {}", name),
+                Outcome::LoseItem { name: None } => "Lost an item".to_string(),
+                Outcome::GainRelic => "Gained a relic".to_string(),
+                Outcome::Status { effect } => format!("Afflicted: {}", effect.name()),
+            }
+        }
+    }
+
+    pub fn spawn_event_screen(mut commands: Commands, events: Res<NarrativeEvents>) {
+        commands.insert_resource(CurrentEvent {
+            index: random_range(0..events.events.len()),
+            result: None,
+        });
+
+        commands
+            .spawn((
+                Name::new("Event Screen"),
+                screen_node(Color::srgb(0.1, 0.08, 0.12)),
+                EventScreen,
+            ))
+            .with_child((
+                Name::new("Event Content"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(CHOICE_GAP),
+                    ..default()
+                },
+                EventContent,
+            ));
+    }
+
+    pub fn despawn_event_screen(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<EventScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+        commands.remove_resource::<CurrentEvent>();
+    }
+
+    fn spawn_choice<'a>(
+        parent: &'a mut ChildBuilder,
+        label: String,
+        enabled: bool,
+    ) -> EntityCommands<'a> {
+        let color = if enabled {
+            CHOICE_COLOR
+        } else {
+            DISABLED_COLOR
+        };
+        let mut choice = parent.spawn(box_node(CHOICE_SIZE, color));
+        choice.with_child(label_node(label, 13.0, Color::WHITE));
+        if enabled {
+            choice
+                .observe(recolor_node_on::<Pointer<Over>>(CHOICE_HOVER_COLOR))
+                .observe(recolor_node_on::<Pointer<Out>>(CHOICE_COLOR));
+        }
+        choice
+    }
+
+    fn text_block(text: String, color: Color) -> impl Bundle {
+        (
+            Text::new(text),
+            TextFont::from_font_size(14.0),
+            TextColor(color),
+            TextLayout::new_with_justify(JustifyText::Center),
+            Node {
+                max_width: Val::Px(TEXT_WIDTH),
+                margin: UiRect::vertical(Val::Px(20.0)),
+                ..default()
+            },
+        )
+    }
+
+    /// Shows the event's choices, then the result once one has been picked.
+    pub fn refresh_event_screen(
+        mut commands: Commands,
+        events: Res<NarrativeEvents>,
+        current: Option<Res<CurrentEvent>>,
+        inventory: Res<Inventory>,
+        new_screen: Query<(), Added<EventContent>>,
+        content_query: Query<Entity, With<EventContent>>,
+    ) {
+        let Some(current) = current else {
+            return;
+        };
+        if !current.is_changed() && new_screen.is_empty() {
+            return;
+        }
+        let Ok(content) = content_query.get_single() else {
+            return;
+        };
+        let event = &events.events[current.index];
+
+        commands.entity(content).despawn_descendants();
+        commands.entity(content).with_children(|parent| {
+            parent.spawn((
+                Text::new(event.title.clone()),
+                TextColor(Color::srgb(1.0, 0.85, 0.5)),
+            ));
+            parent.spawn(text_block(event.text.clone(), Color::WHITE));
+
+            match &current.result {
+                None => {
+                    for (i, choice) in event.choices.iter().enumerate() {
+                        let enabled = choice.is_available(&inventory);
+                        let mut row = spawn_choice(parent, choice.label(), enabled);
+                        if enabled {
+                            row.insert(Focusable)
+                                .observe(pick_choice_on::<Pointer<Click>>(i));
+                        }
+                    }
+                }
+                Some(result) => {
+                    parent.spawn(text_block(result.clone(), Color::srgb(0.8, 0.8, 1.0)));
+                    spawn_choice(parent, "Continue".to_string(), true)
+                        .insert(Focusable)
+                        .observe(leave_event_on::<Pointer<Click>>());
+                }
+            }
+        });
+    }
+
+    fn pick_choice_on<E: Debug + Clone + Reflect>(
+        index: usize,
+    ) -> impl Fn(
+        Trigger<E>,
+        (
+            Res<NarrativeEvents>,
+            ResMut<CurrentEvent>,
+            ResMut<Inventory>,
+            ResMut<Deck>,
+            ResMut<Relics>,
+            Query<
+                (&Stats, &Class, &mut PlayerHealth, &MaxHealth, &mut Effects),
+                With<PlayerEntity>,
+            >,
+        ),
+    ) {
+        move |_ev, (events, mut current, mut inventory, mut deck, mut relics, mut player_query)| {
+            if current.result.is_some() {
+                return;
+            }
+            let Some(choice) = events.events[current.index].choices.get(index) else {
+                return;
+            };
+            let Ok((stats, class, mut health, max_health, mut effects)) =
+                player_query.get_single_mut()
+            else {
+                return;
+            };
+
+            let (outcomes, mut result) = match choice.check {
+                Some(check) => {
+                    let roll = random_range(1..=10);
+                    let total = stats.get(check.stat) + roll;
+                    println!(
+                        "[pick_choice_on] {} check: {} + {} vs {}",
+                        check.stat.name(),
+                        stats.get(check.stat),
+                        roll,
+                        check.difficulty
+                    );
+                    if total >= check.difficulty {
+                        (&choice.outcomes, format!("Success! {}", choice.result))
+                    } else {
+                        (
+                            &choice.failure,
+                            format!("Failed. {}", choice.failure_result),
+                        )
+                    }
+                }
+                None => (&choice.outcomes, choice.result.clone()),
+            };
+
+            for outcome in outcomes {
+                match outcome {
+                    Outcome::Gold { amount } => {
+                        inventory.gold = inventory.gold.saturating_add_signed(*amount);
+                    }
+                    Outcome::Health { amount } => {
+                        // Events can wound but never kill outright
+                        health.0 = (health.0 + amount).clamp(1, max_health.0);
+                    }
+                    Outcome::GainCard => {
+                        let pool = class.card_pool();
+                        let (effect, _) = pool[random_range(0..pool.len())].clone();
+                        deck.cards.push(effect);
+                    }
+                    Outcome::LoseCard => {
+                        if deck.cards.len() > 1 {
+                            let index = random_range(0..deck.cards.len());
+                            deck.cards.remove(index);
+                        }
+                    }
+                    Outcome::GainItem { name, rarity } => {
+                        if !inventory.has_room() {
+                            result.push_str(&format!("\nNo room for {}", name));
+                            continue;
+                        }
+                        inventory.items.push(LootItem {
+                            name: name.clone(),
+                            rarity: *rarity,
+                        });
+                    }
+                    Outcome::LoseItem { name } => {
+                        let position = match name {
+                            Some(name) => {
+                                inventory.items.iter().position(|item| &item.name == name)
+                            }
+                            None if inventory.items.is_empty() => None,
+                            None => Some(random_range(0..inventory.items.len())),
+                        };
+                        if let Some(position) = position {
+                            inventory.items.remove(position);
+                        }
+                    }
+                    Outcome::GainRelic => relics.relics.push(Relic::get_random_relic()),
+                    Outcome::Status { effect } => {
+                        effects.effects.push(effect.to_active_effect());
+                    }
+                }
+                result.push_str(&format!("\n{}", outcome.describe()));
+            }
+            current.result = Some(result);
+        }
+    }
+
+    fn leave_event_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::Battle);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+pub mod new_run {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+
+    use crate::card::card::Deck;
+    use crate::card_play::card_play::PendingTarget;
+    use crate::combat_log::combat_log::CombatLog;
+    use crate::dungeon::dungeon::Dungeon;
+    use crate::focus::focus::{Focus, Focusable};
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::loot::loot::LootPity;
+    use crate::main_menu::main_menu::RunStatus;
+    use crate::progression::progression::Experience;
+    use crate::relics::relics::Relics;
+    use crate::rng::rng::RunRng;
+    use crate::run_stats::run_stats::{RunHistory, RunStats};
+    use crate::skills::skills::Class;
+    use crate::{
+        recolor_node_on, spawn_run_player, BattleEntity, EnemyEntity, GameState, Inventory,
+        PlayerEntity,
+    };
+
+    const CLASS_BUTTON_SIZE: Vec2 = Vec2::new(360.0, 44.0);
+    const BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.4);
+    const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.35, 0.6);
+
+    /// The class picked for the run that is about to start. `start_new_run` takes it when the
+    /// first battle begins.
+    #[derive(Resource)]
+    pub struct NewRun {
+        pub class: Class,
+    }
+
+    #[derive(Component)]
+    pub struct ClassSelectScreen;
+
+    pub fn spawn_class_select(mut commands: Commands, history: Res<RunHistory>) {
+        commands
+            .spawn((
+                Name::new("Class Select Screen"),
+                screen_node(Color::srgb(0.05, 0.05, 0.1)),
+                ClassSelectScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new("Choose a class"),
+                    TextFont::from_font_size(28.0),
+                    TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                ));
+                for class in Class::ALL {
+                    spawn_class_button(parent, class);
+                }
+                parent.spawn((
+                    Text::new(format!(
+                        "{} honey from past runs: start with {} gold",
+                        history.total_honey(),
+                        history.starting_gold()
+                    )),
+                    TextFont::from_font_size(14.0),
+                    TextColor(Color::srgb(1.0, 0.85, 0.2)),
+                ));
+                parent
+                    .spawn((box_node(Vec2::new(140.0, 36.0), BUTTON_COLOR), Focusable))
+                    .with_child(label_node("Back", 16.0, Color::WHITE))
+                    .observe(recolor_node_on::<Pointer<Over>>(BUTTON_HOVER_COLOR))
+                    .observe(recolor_node_on::<Pointer<Out>>(BUTTON_COLOR))
+                    .observe(back_to_main_menu_on::<Pointer<Click>>());
+            });
+    }
+
+    fn spawn_class_button(parent: &mut ChildBuilder, class: Class) {
+        parent
+            .spawn((box_node(CLASS_BUTTON_SIZE, BUTTON_COLOR), Focusable))
+            .with_children(|parent| {
+                parent
+                    .spawn((
+                        Node {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        PickingBehavior::IGNORE,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(label_node(class.name(), 18.0, Color::WHITE));
+                        parent.spawn(label_node(
+                            class.description(),
+                            12.0,
+                            Color::srgb(0.7, 0.7, 0.7),
+                        ));
+                    });
+            })
+            .observe(recolor_node_on::<Pointer<Over>>(BUTTON_HOVER_COLOR))
+            .observe(recolor_node_on::<Pointer<Out>>(BUTTON_COLOR))
+            .observe(choose_class_on::<Pointer<Click>>(class));
+    }
+
+    pub fn despawn_class_select(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<ClassSelectScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    fn choose_class_on<E: Debug + Clone + Reflect>(
+        class: Class,
+    ) -> impl Fn(Trigger<E>, Commands, ResMut<NextState<GameState>>) {
+        move |_ev, mut commands, mut next_state| {
+            commands.insert_resource(NewRun { class });
+            next_state.set(GameState::Battle);
+        }
+    }
+
+    fn back_to_main_menu_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::MainMenu);
+        }
+    }
+
+    /// Throws away everything left over from the previous run, player and all, and sets up
+    /// a fresh one for the chosen class. Does nothing when a battle starts mid-run.
+    pub fn start_new_run(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        new_run: Option<Res<NewRun>>,
+        history: Res<RunHistory>,
+        mut run_status: ResMut<RunStatus>,
+        run_entity_query: Query<
+            Entity,
+            Or<(With<PlayerEntity>, With<EnemyEntity>, With<BattleEntity>)>,
+        >,
+    ) {
+        let Some(new_run) = new_run else {
+            return;
+        };
+        let class = new_run.class;
+        println!("[start_new_run] Starting a new {} run", class.name());
+        commands.remove_resource::<NewRun>();
+
+        for entity in run_entity_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        commands.insert_resource(Deck {
+            cards: class.starting_deck(),
+        });
+        commands.insert_resource(Inventory {
+            gold: history.starting_gold(),
+            ..default()
+        });
+        commands.insert_resource(Relics::default());
+        commands.insert_resource(Dungeon::default());
+        commands.insert_resource(LootPity::default());
+        commands.insert_resource(Experience::default());
+        commands.insert_resource(RunRng::default());
+        commands.insert_resource(CombatLog::default());
+        commands.insert_resource(PendingTarget::default());
+        commands.insert_resource(RunStats::default());
+        commands.insert_resource(Focus::default());
+
+        spawn_run_player(&mut commands, &asset_server, class);
+        run_status.started = true;
+    }
+}
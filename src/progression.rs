@@ -0,0 +1,247 @@
+pub mod progression {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+
+    use crate::combat::combat::CombatEvent;
+    use crate::dungeon::dungeon::Dungeon;
+    use crate::focus::focus::Focusable;
+    use crate::layout::layout::{box_node, label_node, screen_node, ScreenAnchor};
+    use crate::skills::skills::{StatKind, Stats};
+    use crate::{recolor_node_on, GameState, PlayerEntity};
+
+    const STAT_POINTS_PER_LEVEL: usize = 3;
+    const XP_PER_LEVEL: u32 = 40;
+    const ROW_SIZE: Vec2 = Vec2::new(260.0, 30.0);
+    const ROW_GAP: f32 = 10.0;
+    const ROW_COLOR: Color = Color::srgb(0.2, 0.3, 0.45);
+    const ROW_HOVER_COLOR: Color = Color::srgb(0.3, 0.45, 0.65);
+    const DISABLED_COLOR: Color = Color::srgba(0.3, 0.3, 0.3, 0.7);
+
+    /// The player's level for this run and the stat points they haven't spent yet.
+    #[derive(Resource, Debug)]
+    pub struct Experience {
+        pub level: usize,
+        pub xp: u32,
+        pub unspent_points: usize,
+        /// Set when a level is gained and cleared once the level-up screen is closed, so points
+        /// left over after Done wait for the next level-up instead of reopening the screen.
+        pub level_up_pending: bool,
+    }
+
+    impl Default for Experience {
+        fn default() -> Self {
+            Experience {
+                level: 1,
+                xp: 0,
+                unspent_points: 0,
+                level_up_pending: false,
+            }
+        }
+    }
+
+    impl Experience {
+        pub fn xp_to_next_level(&self) -> u32 {
+            XP_PER_LEVEL * self.level as u32
+        }
+
+        /// Adds XP and returns how many levels were gained.
+        pub fn gain(&mut self, amount: u32) -> usize {
+            self.xp += amount;
+            let mut levels = 0;
+            while self.xp >= self.xp_to_next_level() {
+                self.xp -= self.xp_to_next_level();
+                self.level += 1;
+                self.unspent_points += STAT_POINTS_PER_LEVEL;
+                self.level_up_pending = true;
+                levels += 1;
+            }
+            levels
+        }
+
+        /// Where the loot screen leads: spend new stat points first, then pick a card.
+        pub fn next_state_after_loot(&self) -> GameState {
+            if self.level_up_pending {
+                GameState::LevelUp
+            } else {
+                GameState::CardReward
+            }
+        }
+    }
+
+    #[derive(Component)]
+    pub struct LevelText;
+
+    #[derive(Component)]
+    pub struct LevelUpScreen;
+
+    #[derive(Component)]
+    pub struct LevelUpContent;
+
+    pub fn award_experience(
+        mut combat_events: EventReader<CombatEvent>,
+        dungeon: Res<Dungeon>,
+        mut experience: ResMut<Experience>,
+    ) {
+        for event in combat_events.read() {
+            if !matches!(event, CombatEvent::EnemyKilled) {
+                continue;
+            }
+            let amount =
+                (dungeon.encounter.base_xp() as f32 * dungeon.rank.xp_multiplier()).round() as u32;
+            let levels = experience.gain(amount);
+            println!("[award_experience] +{} XP", amount);
+            if levels > 0 {
+                println!("[award_experience] Reached level {}", experience.level);
+            }
+        }
+    }
+
+    pub fn spawn_level_text(mut commands: Commands) {
+        commands.spawn((
+            Name::new("Level Text"),
+            Text2d::new("Lv 1"),
+            TextFont::from_font_size(14.0),
+            TextColor(Color::srgb(0.6, 0.8, 1.0)),
+            Transform::from_xyz(0.0, 0.0, 0.95),
+            ScreenAnchor::new(Vec2::ONE, Vec2::new(-70.0, -55.0)),
+            LevelText,
+        ));
+    }
+
+    pub fn update_level_text(
+        experience: Res<Experience>,
+        mut level_text_query: Query<&mut Text2d, With<LevelText>>,
+    ) {
+        if !experience.is_changed() {
+            return;
+        }
+        for mut text in level_text_query.iter_mut() {
+            text.0 = format!(
+                "Lv {}  {}/{} XP",
+                experience.level,
+                experience.xp,
+                experience.xp_to_next_level()
+            );
+        }
+    }
+
+    pub fn spawn_level_up_screen(mut commands: Commands) {
+        commands
+            .spawn((
+                Name::new("Level Up Screen"),
+                screen_node(Color::srgb(0.05, 0.08, 0.15)),
+                LevelUpScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Name::new("Level Up Content"),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(ROW_GAP),
+                        margin: UiRect::bottom(Val::Px(20.0)),
+                        ..default()
+                    },
+                    LevelUpContent,
+                ));
+
+                parent
+                    .spawn((
+                        Name::new("Level Up Done Button"),
+                        box_node(Vec2::new(120.0, 36.0), Color::srgb(0.4, 0.4, 0.4)),
+                    ))
+                    .with_child(label_node("Done", 16.0, Color::WHITE))
+                    .observe(recolor_node_on::<Pointer<Over>>(Color::srgb(0.6, 0.6, 0.6)))
+                    .observe(recolor_node_on::<Pointer<Out>>(Color::srgb(0.4, 0.4, 0.4)))
+                    .insert(Focusable)
+                    .observe(finish_level_up_on::<Pointer<Click>>());
+            });
+    }
+
+    pub fn despawn_level_up_screen(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<LevelUpScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    /// Rebuilds the stat list whenever a point is spent.
+    pub fn refresh_level_up_screen(
+        mut commands: Commands,
+        experience: Res<Experience>,
+        stats_query: Query<Ref<Stats>, With<PlayerEntity>>,
+        new_screen: Query<(), Added<LevelUpContent>>,
+        content_query: Query<Entity, With<LevelUpContent>>,
+    ) {
+        let Ok(stats) = stats_query.get_single() else {
+            return;
+        };
+        if !experience.is_changed() && !stats.is_changed() && new_screen.is_empty() {
+            return;
+        }
+        let Ok(content) = content_query.get_single() else {
+            return;
+        };
+
+        commands.entity(content).despawn_descendants();
+        commands.entity(content).with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("Level {}!", experience.level)),
+                TextColor(Color::srgb(0.6, 0.8, 1.0)),
+            ));
+            parent.spawn((
+                Text::new(format!("Stat points: {}", experience.unspent_points)),
+                TextFont::from_font_size(14.0),
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+
+            let can_spend = experience.unspent_points > 0;
+            for stat in StatKind::ALL {
+                let color = if can_spend { ROW_COLOR } else { DISABLED_COLOR };
+                let mut row = parent.spawn(box_node(ROW_SIZE, color));
+                row.with_child(label_node(
+                    format!("{}: {}  [+]", stat.name(), stats.get(stat)),
+                    14.0,
+                    Color::WHITE,
+                ));
+                if can_spend {
+                    row.observe(recolor_node_on::<Pointer<Over>>(ROW_HOVER_COLOR))
+                        .observe(recolor_node_on::<Pointer<Out>>(ROW_COLOR))
+                        .insert(Focusable)
+                        .observe(spend_point_on::<Pointer<Click>>(stat));
+                }
+            }
+        });
+    }
+
+    fn spend_point_on<E: Debug + Clone + Reflect>(
+        stat: StatKind,
+    ) -> impl Fn(Trigger<E>, (ResMut<Experience>, Query<&mut Stats, With<PlayerEntity>>)) {
+        move |_ev, (mut experience, mut stats_query)| {
+            if experience.unspent_points == 0 {
+                return;
+            }
+            let Ok(mut stats) = stats_query.get_single_mut() else {
+                return;
+            };
+            *stats.get_mut(stat) += 1;
+            experience.unspent_points -= 1;
+        }
+    }
+
+    /// Unspent points are kept for the next level-up.
+    fn finish_level_up_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<Experience>, ResMut<NextState<GameState>>) {
+        move |_ev, mut experience, mut next_state| {
+            experience.level_up_pending = false;
+            next_state.set(GameState::CardReward);
+        }
+    }
+}
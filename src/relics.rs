@@ -0,0 +1,176 @@
+pub mod relics {
+    use bevy::prelude::*;
+
+    use crate::card::card::CardEffect;
+    use crate::combat::combat::{apply_card_effect, CombatEvent, Combatant, CombatantEffects};
+    use crate::{MaxHealth, PlayerEntity, PlayerHealth};
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum RelicTrigger {
+        BattleStart,
+        CardPlayed,
+        DamageDealt,
+        StatusApplied,
+        EnemyKilled,
+        /// Fires once per battle when the player's health drops below this fraction of max.
+        HealthBelow(f32),
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct Relic {
+        pub name: String,
+        pub description: String,
+        pub trigger: RelicTrigger,
+        pub effect: CardEffect,
+        triggered_this_battle: bool,
+    }
+
+    impl Relic {
+        pub fn new(
+            name: &str,
+            description: &str,
+            trigger: RelicTrigger,
+            effect: CardEffect,
+        ) -> Relic {
+            Relic {
+                name: name.to_string(),
+                description: description.to_string(),
+                trigger,
+                effect,
+                triggered_this_battle: false,
+            }
+        }
+
+        // Relic effects go through `apply_card_effect` like cards do, so one relic can set off
+        // another: Metronome's stun is a status, which fires Sharpened Whetstone, whose damage
+        // fires Leech Tooth. That's on purpose, but the chains have to end: no relic's effect
+        // may lead back to its own trigger, directly or through other relics, or they would
+        // keep re-triggering each other every frame. Check the chain when adding a relic.
+        pub fn all() -> Vec<Relic> {
+            vec![
+                Relic::new(
+                    "Ember Heart",
+                    "Deal 5 damage at the start of each battle.",
+                    RelicTrigger::BattleStart,
+                    CardEffect::DirectDamage(5),
+                ),
+                Relic::new(
+                    "Sharpened Whetstone",
+                    "Whenever you apply a status, deal 3 damage.",
+                    RelicTrigger::StatusApplied,
+                    CardEffect::DirectDamage(3),
+                ),
+                Relic::new(
+                    "Leech Tooth",
+                    "Heal 1 whenever you deal damage.",
+                    RelicTrigger::DamageDealt,
+                    CardEffect::Heal(1),
+                ),
+                Relic::new(
+                    "Hunter's Trophy",
+                    "Heal 10 after killing an enemy.",
+                    RelicTrigger::EnemyKilled,
+                    CardEffect::Heal(10),
+                ),
+                Relic::new(
+                    "Last Stand",
+                    "Heal 20 the first time you drop below 30% health in a battle.",
+                    RelicTrigger::HealthBelow(0.3),
+                    CardEffect::Heal(20),
+                ),
+                Relic::new(
+                    "Metronome",
+                    "Stun the enemy for 0.5s every time you play a card.",
+                    RelicTrigger::CardPlayed,
+                    CardEffect::Stun { duration: 0.5 },
+                ),
+            ]
+        }
+
+        pub fn get_random_relic() -> Relic {
+            use rand::random_range;
+
+            let mut relics = Relic::all();
+            let index = random_range(0..relics.len());
+            relics.swap_remove(index)
+        }
+
+        fn matches(&self, event: &CombatEvent, health_fraction: f32) -> bool {
+            match (&self.trigger, event) {
+                (RelicTrigger::BattleStart, CombatEvent::BattleStarted) => true,
+                (RelicTrigger::EnemyKilled, CombatEvent::EnemyKilled) => true,
+                (
+                    RelicTrigger::CardPlayed,
+                    CombatEvent::CardPlayed {
+                        owner: Combatant::Player,
+                        ..
+                    },
+                ) => true,
+                (
+                    RelicTrigger::DamageDealt,
+                    CombatEvent::DamageDealt {
+                        target: Combatant::Enemy,
+                        ..
+                    },
+                ) => true,
+                (
+                    RelicTrigger::StatusApplied,
+                    CombatEvent::StatusApplied {
+                        target: Combatant::Enemy,
+                        ..
+                    },
+                ) => true,
+                (
+                    RelicTrigger::HealthBelow(threshold),
+                    CombatEvent::DamageDealt {
+                        target: Combatant::Player,
+                        ..
+                    },
+                ) => !self.triggered_this_battle && health_fraction < *threshold,
+                _ => false,
+            }
+        }
+    }
+
+    #[derive(Resource, Default)]
+    pub struct Relics {
+        pub relics: Vec<Relic>,
+    }
+
+    pub fn trigger_relics(
+        mut combat_events: ParamSet<(EventReader<CombatEvent>, EventWriter<CombatEvent>)>,
+        mut relics: ResMut<Relics>,
+        player_health_query: Query<(&PlayerHealth, &MaxHealth), With<PlayerEntity>>,
+        mut effects: CombatantEffects,
+    ) {
+        let health_fraction = player_health_query
+            .get_single()
+            .map(|(health, max_health)| health.0 as f32 / max_health.0 as f32)
+            .unwrap_or(1.0);
+
+        let mut fired = vec![];
+        for event in combat_events.p0().read() {
+            if matches!(event, CombatEvent::BattleStarted) {
+                for relic in relics.relics.iter_mut() {
+                    relic.triggered_this_battle = false;
+                }
+            }
+            for relic in relics.relics.iter_mut() {
+                if relic.matches(event, health_fraction) {
+                    println!("[trigger_relics] {} triggered by {:?}", relic.name, event);
+                    relic.triggered_this_battle = true;
+                    fired.push(relic.effect.clone());
+                }
+            }
+        }
+
+        for effect in fired {
+            apply_card_effect(
+                Combatant::Player,
+                &effect,
+                &mut effects,
+                &mut combat_events.p1(),
+            );
+        }
+    }
+}
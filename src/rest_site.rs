@@ -0,0 +1,296 @@
+pub mod rest_site {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+
+    use crate::card::card::Deck;
+    use crate::focus::focus::Focusable;
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::skills::skills::{StatKind, Stats};
+    use crate::{recolor_node_on, GameState, MaxHealth, PlayerEntity, PlayerHealth};
+
+    /// Fraction of max health restored by resting.
+    const REST_HEAL_FRACTION: f32 = 0.3;
+    const TRAINING_POINTS: usize = 2;
+    const OPTION_SIZE: Vec2 = Vec2::new(160.0, 60.0);
+    const OPTION_GAP: f32 = 30.0;
+    const ROW_SIZE: Vec2 = Vec2::new(320.0, 28.0);
+    const ROW_GAP: f32 = 4.0;
+    /// Cards per page of the upgrade list; bigger decks get page buttons below it.
+    const MAX_LISTED: usize = 8;
+    const PAGE_BUTTON_SIZE: Vec2 = Vec2::new(40.0, 28.0);
+    const OPTION_COLOR: Color = Color::srgb(0.25, 0.35, 0.25);
+    const OPTION_HOVER_COLOR: Color = Color::srgb(0.35, 0.5, 0.35);
+
+    #[derive(Clone, Copy, Default, PartialEq, Eq)]
+    enum RestChoice {
+        #[default]
+        Choosing,
+        Upgrading,
+        Training,
+    }
+
+    /// Which page of the rest site is showing.
+    #[derive(Resource, Default)]
+    pub struct RestSite {
+        choice: RestChoice,
+        /// Page of the upgrade list being shown.
+        page: usize,
+    }
+
+    #[derive(Component)]
+    pub struct RestSiteScreen;
+
+    #[derive(Component)]
+    pub struct RestSiteContent;
+
+    pub fn spawn_rest_site(mut commands: Commands) {
+        commands.insert_resource(RestSite::default());
+
+        commands
+            .spawn((
+                Name::new("Rest Site Screen"),
+                screen_node(Color::srgb(0.08, 0.12, 0.08)),
+                RestSiteScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new("Rest Site"),
+                    TextColor(Color::WHITE),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(20.0)),
+                        ..default()
+                    },
+                ));
+                parent.spawn((
+                    Name::new("Rest Site Content"),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(ROW_GAP),
+                        ..default()
+                    },
+                    RestSiteContent,
+                ));
+            });
+    }
+
+    pub fn despawn_rest_site(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<RestSiteScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+        commands.remove_resource::<RestSite>();
+    }
+
+    fn spawn_button<'a>(
+        parent: &'a mut ChildBuilder,
+        size: Vec2,
+        label: String,
+    ) -> EntityCommands<'a> {
+        let mut button = parent.spawn(box_node(size, OPTION_COLOR));
+        button
+            .with_child(label_node(label, 13.0, Color::WHITE))
+            .observe(recolor_node_on::<Pointer<Over>>(OPTION_HOVER_COLOR))
+            .observe(recolor_node_on::<Pointer<Out>>(OPTION_COLOR));
+        button
+    }
+
+    fn spawn_heading(parent: &mut ChildBuilder, text: String) {
+        parent.spawn((
+            Text::new(text),
+            TextFont::from_font_size(14.0),
+            TextColor(Color::WHITE),
+        ));
+    }
+
+    /// Rebuilds the page whenever the player switches between the options.
+    pub fn refresh_rest_site(
+        mut commands: Commands,
+        rest_site: Option<Res<RestSite>>,
+        deck: Res<Deck>,
+        player_query: Query<(&PlayerHealth, &MaxHealth, &Stats), With<PlayerEntity>>,
+        new_screen: Query<(), Added<RestSiteContent>>,
+        content_query: Query<Entity, With<RestSiteContent>>,
+    ) {
+        let Some(rest_site) = rest_site else {
+            return;
+        };
+        if !rest_site.is_changed() && new_screen.is_empty() {
+            return;
+        }
+        let Ok(content) = content_query.get_single() else {
+            return;
+        };
+        let Ok((health, max_health, stats)) = player_query.get_single() else {
+            return;
+        };
+
+        commands.entity(content).despawn_descendants();
+        commands
+            .entity(content)
+            .with_children(|parent| match rest_site.choice {
+                RestChoice::Choosing => {
+                    let heal = (max_health.0 as f32 * REST_HEAL_FRACTION).round() as i32;
+                    spawn_heading(parent, format!("Health: {}/{}", health.0, max_health.0));
+                    parent
+                        .spawn(Node {
+                            column_gap: Val::Px(OPTION_GAP),
+                            margin: UiRect::top(Val::Px(20.0)),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_button(parent, OPTION_SIZE, format!("Rest\nHeal {} HP", heal))
+                                .insert(Focusable)
+                                .observe(rest_on::<Pointer<Click>>());
+                            spawn_button(parent, OPTION_SIZE, "Upgrade\na card".to_string())
+                                .insert(Focusable)
+                                .observe(switch_choice_on::<Pointer<Click>>(RestChoice::Upgrading));
+                            spawn_button(
+                                parent,
+                                OPTION_SIZE,
+                                format!("Train\n+{} to a stat", TRAINING_POINTS),
+                            )
+                            .insert(Focusable)
+                            .observe(switch_choice_on::<Pointer<Click>>(RestChoice::Training));
+                        });
+                }
+                RestChoice::Upgrading => {
+                    spawn_heading(parent, "Pick a card to upgrade".to_string());
+                    let last_page = deck.cards.len().saturating_sub(1) / MAX_LISTED;
+                    let page = rest_site.page.min(last_page);
+                    let first = page * MAX_LISTED;
+                    for (i, effect) in deck.cards.iter().enumerate().skip(first).take(MAX_LISTED) {
+                        spawn_button(
+                            parent,
+                            ROW_SIZE,
+                            format!("{} -> {}", effect.describe(), effect.upgraded().describe()),
+                        )
+                        .insert(Focusable)
+                        .observe(upgrade_card_on::<Pointer<Click>>(i));
+                    }
+                    if last_page > 0 {
+                        spawn_page_buttons(parent, page, last_page + 1);
+                    }
+                    spawn_back_button(parent);
+                }
+                RestChoice::Training => {
+                    spawn_heading(parent, "Pick a stat to train".to_string());
+                    for stat in StatKind::ALL {
+                        spawn_button(
+                            parent,
+                            ROW_SIZE,
+                            format!(
+                                "{}: {} -> {}",
+                                stat.name(),
+                                stats.get(stat),
+                                stats.get(stat) + TRAINING_POINTS
+                            ),
+                        )
+                        .insert(Focusable)
+                        .observe(train_stat_on::<Pointer<Click>>(stat));
+                    }
+                    spawn_back_button(parent);
+                }
+            });
+    }
+
+    fn spawn_page_buttons(parent: &mut ChildBuilder, page: usize, pages: usize) {
+        parent
+            .spawn(Node {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(16.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                if page > 0 {
+                    spawn_button(parent, PAGE_BUTTON_SIZE, "<".to_string())
+                        .insert(Focusable)
+                        .observe(show_page_on::<Pointer<Click>>(page - 1));
+                }
+                parent.spawn((
+                    Text::new(format!("Page {}/{}", page + 1, pages)),
+                    TextFont::from_font_size(13.0),
+                    TextColor(Color::WHITE),
+                ));
+                if page + 1 < pages {
+                    spawn_button(parent, PAGE_BUTTON_SIZE, ">".to_string())
+                        .insert(Focusable)
+                        .observe(show_page_on::<Pointer<Click>>(page + 1));
+                }
+            });
+    }
+
+    fn show_page_on<E: Debug + Clone + Reflect>(
+        page: usize,
+    ) -> impl Fn(Trigger<E>, ResMut<RestSite>) {
+        move |_ev, mut rest_site| {
+            rest_site.page = page;
+        }
+    }
+
+    fn spawn_back_button(parent: &mut ChildBuilder) {
+        spawn_button(parent, Vec2::new(120.0, 36.0), "Back".to_string())
+            .insert(Focusable)
+            .observe(switch_choice_on::<Pointer<Click>>(RestChoice::Choosing));
+    }
+
+    fn switch_choice_on<E: Debug + Clone + Reflect>(
+        choice: RestChoice,
+    ) -> impl Fn(Trigger<E>, ResMut<RestSite>) {
+        move |_ev, mut rest_site| {
+            rest_site.choice = choice;
+            rest_site.page = 0;
+        }
+    }
+
+    fn rest_on<E: Debug + Clone + Reflect>() -> impl Fn(
+        Trigger<E>,
+        (
+            Query<(&mut PlayerHealth, &MaxHealth), With<PlayerEntity>>,
+            ResMut<NextState<GameState>>,
+        ),
+    ) {
+        move |_ev, (mut player_query, mut next_state)| {
+            if let Ok((mut health, max_health)) = player_query.get_single_mut() {
+                let heal = (max_health.0 as f32 * REST_HEAL_FRACTION).round() as i32;
+                health.0 = (health.0 + heal).min(max_health.0);
+                println!("[rest_on] Rested to {}/{}", health.0, max_health.0);
+            }
+            next_state.set(GameState::Battle);
+        }
+    }
+
+    fn upgrade_card_on<E: Debug + Clone + Reflect>(
+        index: usize,
+    ) -> impl Fn(Trigger<E>, (ResMut<Deck>, ResMut<NextState<GameState>>)) {
+        move |_ev, (mut deck, mut next_state)| {
+            let Some(card) = deck.cards.get_mut(index) else {
+                return;
+            };
+            *card = card.upgraded();
+            println!("[upgrade_card_on] Upgraded to {}", card.describe());
+            next_state.set(GameState::Battle);
+        }
+    }
+
+    fn train_stat_on<E: Debug + Clone + Reflect>(
+        stat: StatKind,
+    ) -> impl Fn(
+        Trigger<E>,
+        (
+            Query<&mut Stats, With<PlayerEntity>>,
+            ResMut<NextState<GameState>>,
+        ),
+    ) {
+        move |_ev, (mut stats_query, mut next_state)| {
+            if let Ok(mut stats) = stats_query.get_single_mut() {
+                *stats.get_mut(stat) += TRAINING_POINTS;
+                println!("[train_stat_on] {} is now {}", stat.name(), stats.get(stat));
+            }
+            next_state.set(GameState::Battle);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+pub mod rng {
+    use bevy::prelude::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// Environment variable that fixes the seed of every run, e.g. `RUN_SEED=42`.
+    const SEED_ENV_VAR: &str = "RUN_SEED";
+
+    /// Seeded RNG for the current run, so the same seed plays out the same rolls.
+    #[derive(Resource)]
+    pub struct RunRng {
+        pub seed: u64,
+        rng: StdRng,
+    }
+
+    /// A random seed unless one was given with `--seed <n>` or the `RUN_SEED` variable, so
+    /// a run's rolls can be played back.
+    impl Default for RunRng {
+        fn default() -> Self {
+            RunRng::from_seed(seed_override().unwrap_or_else(rand::random))
+        }
+    }
+
+    fn seed_override() -> Option<u64> {
+        let mut args = std::env::args().skip_while(|arg| arg != "--seed").skip(1);
+        let seed = args.next().or_else(|| std::env::var(SEED_ENV_VAR).ok())?;
+        seed.parse()
+            .inspect_err(|err| println!("[RunRng] Ignoring seed {:?}: {}", seed, err))
+            .ok()
+    }
+
+    impl RunRng {
+        pub fn from_seed(seed: u64) -> RunRng {
+            println!("[RunRng] Run seed: {}", seed);
+            RunRng {
+                seed,
+                rng: StdRng::seed_from_u64(seed),
+            }
+        }
+
+        /// Returns true with the given probability.
+        pub fn chance(&mut self, probability: f32) -> bool {
+            self.rng.random::<f32>() < probability
+        }
+    }
+}
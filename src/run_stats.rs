@@ -0,0 +1,431 @@
+pub mod run_stats {
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use bevy::prelude::*;
+    use serde::{Deserialize, Serialize};
+
+    use crate::combat::combat::{CombatEvent, Combatant};
+    use crate::dungeon::dungeon::Dungeon;
+    use crate::focus::focus::Focusable;
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::main_menu::main_menu::RunStatus;
+    use crate::rng::rng::RunRng;
+    use crate::skills::skills::Class;
+    use crate::{recolor_node_on, GameState, PlayerEntity};
+
+    const HISTORY_PATH: &str = "run_history.json";
+    /// How many past runs the game over screen lists.
+    const RECENT_RUNS: usize = 5;
+    /// How many past runs the profile screen lists.
+    const PROFILE_RUNS: usize = 10;
+    const BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.4);
+    const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.35, 0.6);
+    const HONEY_PER_FLOOR: u32 = 10;
+    const HONEY_PER_KILL: u32 = 3;
+    /// How much honey from past runs buys one gold at the start of a new run.
+    const HONEY_PER_STARTING_GOLD: u32 = 5;
+
+    /// Running totals for the current run, turned into a `RunSummary` when it ends.
+    #[derive(Resource, Default)]
+    pub struct RunStats {
+        enemies_defeated: u32,
+        damage_dealt: i32,
+        damage_taken: i32,
+        cards_played: HashMap<&'static str, u32>,
+        /// Seconds of real time spent in the run, not counting menus or the pause screen.
+        time_played: f32,
+        /// The enemy card or status that last hurt the player.
+        last_hit: Option<&'static str>,
+        last_enemy_card: Option<&'static str>,
+        last_status_on_player: Option<&'static str>,
+    }
+
+    impl RunStats {
+        /// The player's favourite card, picking alphabetically between ties so it's stable.
+        fn most_used_card(&self) -> Option<(String, u32)> {
+            self.cards_played
+                .iter()
+                .max_by(|(a_name, a_count), (b_name, b_count)| {
+                    a_count.cmp(b_count).then_with(|| b_name.cmp(a_name))
+                })
+                .map(|(name, count)| (name.to_string(), *count))
+        }
+    }
+
+    /// How a finished run went, as shown on the game over screen and kept in the history.
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct RunSummary {
+        /// Unix time the run ended.
+        pub finished_at: u64,
+        /// Seed of `RunRng`, which only drives hit rolls; encounters and loot aren't seeded.
+        pub seed: u64,
+        pub class: String,
+        pub floors_cleared: usize,
+        pub enemies_defeated: u32,
+        pub damage_dealt: i32,
+        pub damage_taken: i32,
+        pub most_used_card: Option<(String, u32)>,
+        pub cause_of_death: String,
+        pub time_played: f32,
+        /// Meta progression currency the run earned.
+        pub honey_earned: u32,
+    }
+
+    impl RunSummary {
+        pub fn time_label(&self) -> String {
+            time_label(self.time_played)
+        }
+
+        /// One line for the lists of past runs.
+        fn short_label(&self) -> String {
+            format!(
+                "{}  floor {}  {} kills  {}  +{} honey",
+                self.class,
+                self.floors_cleared,
+                self.enemies_defeated,
+                self.time_label(),
+                self.honey_earned
+            )
+        }
+    }
+
+    fn time_label(time_played: f32) -> String {
+        let seconds = time_played as u32;
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+
+    /// Every finished run, oldest first, kept in `run_history.json`.
+    #[derive(Resource, Serialize, Deserialize, Default)]
+    pub struct RunHistory {
+        pub runs: Vec<RunSummary>,
+    }
+
+    impl RunHistory {
+        pub fn load() -> RunHistory {
+            let Ok(contents) = std::fs::read_to_string(HISTORY_PATH) else {
+                return RunHistory::default();
+            };
+            serde_json::from_str(&contents).unwrap_or_else(|err| {
+                println!("[run_stats] Ignoring broken {}: {}", HISTORY_PATH, err);
+                RunHistory::default()
+            })
+        }
+
+        pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+            std::fs::write(HISTORY_PATH, serde_json::to_string_pretty(self)?)?;
+            Ok(())
+        }
+
+        pub fn total_honey(&self) -> u32 {
+            self.runs.iter().map(|run| run.honey_earned).sum()
+        }
+
+        /// Gold a new run starts with, bought with all the honey earned so far.
+        pub fn starting_gold(&self) -> u32 {
+            self.total_honey() / HONEY_PER_STARTING_GOLD
+        }
+    }
+
+    /// Runs after `Update`, so the blow that kills the player is counted before the game
+    /// over screen reads the stats.
+    pub fn record_run_stats(
+        mut combat_events: EventReader<CombatEvent>,
+        mut stats: ResMut<RunStats>,
+    ) {
+        for event in combat_events.read() {
+            match event {
+                CombatEvent::CardPlayed {
+                    owner: Combatant::Player,
+                    effect,
+                } => *stats.cards_played.entry(effect.name()).or_default() += 1,
+                CombatEvent::CardPlayed {
+                    owner: Combatant::Enemy,
+                    effect,
+                } => stats.last_enemy_card = Some(effect.name()),
+                CombatEvent::StatusApplied {
+                    target: Combatant::Player,
+                    effect,
+                } => stats.last_status_on_player = Some(effect.name()),
+                CombatEvent::DamageDealt {
+                    target: Combatant::Enemy,
+                    amount,
+                    ..
+                } => stats.damage_dealt += amount,
+                CombatEvent::DamageDealt {
+                    target: Combatant::Player,
+                    amount,
+                    over_time,
+                } => {
+                    stats.damage_taken += amount;
+                    stats.last_hit = if *over_time {
+                        stats.last_status_on_player
+                    } else {
+                        stats.last_enemy_card
+                    };
+                }
+                CombatEvent::EnemyKilled => stats.enemies_defeated += 1,
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether the game is somewhere inside a run rather than on a menu.
+    pub fn in_run(state: Res<State<GameState>>) -> bool {
+        !matches!(
+            state.get(),
+            GameState::Title
+                | GameState::MainMenu
+                | GameState::Profile
+                | GameState::Settings
+                | GameState::Controls
+                | GameState::ClassSelect
+                | GameState::GameOver
+        )
+    }
+
+    pub fn tick_play_time(time: Res<Time<Real>>, mut stats: ResMut<RunStats>) {
+        stats.time_played += time.delta_secs();
+    }
+
+    /// Sums up the run that just ended and adds it to the history. The run can't be continued
+    /// from the main menu after this.
+    pub fn finish_run(
+        mut stats: ResMut<RunStats>,
+        mut history: ResMut<RunHistory>,
+        mut run_status: ResMut<RunStatus>,
+        dungeon: Res<Dungeon>,
+        rng: Res<RunRng>,
+        class_query: Query<&Class, With<PlayerEntity>>,
+    ) {
+        let floors_cleared = dungeon.floor.saturating_sub(1);
+        let killer = dungeon.encounter.name();
+        let cause_of_death = match stats.last_hit {
+            Some(hit) => format!("Killed by a {}'s {}", killer, hit),
+            None => format!("Killed by a {}", killer),
+        };
+        let summary = RunSummary {
+            finished_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+            seed: rng.seed,
+            class: class_query
+                .get_single()
+                .map(|class| class.name().to_string())
+                .unwrap_or_default(),
+            floors_cleared,
+            enemies_defeated: stats.enemies_defeated,
+            damage_dealt: stats.damage_dealt,
+            damage_taken: stats.damage_taken,
+            most_used_card: stats.most_used_card(),
+            cause_of_death,
+            time_played: stats.time_played,
+            honey_earned: floors_cleared as u32 * HONEY_PER_FLOOR
+                + stats.enemies_defeated * HONEY_PER_KILL,
+        };
+        history.runs.push(summary);
+        if let Err(err) = history.save() {
+            println!("[finish_run] Could not save run history: {}", err);
+        }
+        *stats = RunStats::default();
+        run_status.started = false;
+    }
+
+    fn stat_row(parent: &mut ChildBuilder, label: &str, value: String) {
+        parent
+            .spawn(Node {
+                width: Val::Px(320.0),
+                justify_content: JustifyContent::SpaceBetween,
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(label),
+                    TextFont::from_font_size(14.0),
+                    TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                ));
+                parent.spawn((
+                    Text::new(value),
+                    TextFont::from_font_size(14.0),
+                    TextColor(Color::WHITE),
+                ));
+            });
+    }
+
+    /// The latest run's numbers, followed by a short list of the runs before it.
+    pub fn spawn_run_summary(parent: &mut ChildBuilder, history: &RunHistory) {
+        let Some((summary, earlier)) = history.runs.split_last() else {
+            return;
+        };
+
+        parent.spawn((
+            Text::new(summary.cause_of_death.clone()),
+            TextFont::from_font_size(16.0),
+            TextColor(Color::srgb(1.0, 0.6, 0.5)),
+        ));
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                margin: UiRect::vertical(Val::Px(8.0)),
+                ..default()
+            })
+            .with_children(|parent| {
+                stat_row(parent, "Class", summary.class.clone());
+                stat_row(parent, "Floors cleared", summary.floors_cleared.to_string());
+                stat_row(
+                    parent,
+                    "Enemies defeated",
+                    summary.enemies_defeated.to_string(),
+                );
+                stat_row(parent, "Damage dealt", summary.damage_dealt.to_string());
+                stat_row(parent, "Damage taken", summary.damage_taken.to_string());
+                stat_row(
+                    parent,
+                    "Most used card",
+                    match &summary.most_used_card {
+                        Some((name, count)) => format!("{} (x{})", name, count),
+                        None => "-".to_string(),
+                    },
+                );
+                stat_row(parent, "Time played", summary.time_label());
+                stat_row(parent, "Combat seed", summary.seed.to_string());
+                stat_row(
+                    parent,
+                    "Honey earned",
+                    format!("{} (total {})", summary.honey_earned, history.total_honey()),
+                );
+            });
+
+        if earlier.is_empty() {
+            return;
+        }
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(2.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new("Previous runs"),
+                    TextFont::from_font_size(14.0),
+                    TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                ));
+                for run in earlier.iter().rev().take(RECENT_RUNS) {
+                    parent.spawn((
+                        Text::new(run.short_label()),
+                        TextFont::from_font_size(12.0),
+                        TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                    ));
+                }
+            });
+    }
+
+    #[derive(Component)]
+    pub struct ProfileScreen;
+
+    /// Totals over every finished run, the honey banked for the next one and the latest runs.
+    pub fn spawn_profile_screen(mut commands: Commands, history: Res<RunHistory>) {
+        commands
+            .spawn((
+                Name::new("Profile Screen"),
+                screen_node(Color::srgb(0.05, 0.05, 0.1)),
+                ProfileScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new("Profile"),
+                    TextFont::from_font_size(28.0),
+                    TextColor(Color::WHITE),
+                ));
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(2.0),
+                        margin: UiRect::vertical(Val::Px(8.0)),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        let runs = &history.runs;
+                        stat_row(parent, "Runs played", runs.len().to_string());
+                        stat_row(
+                            parent,
+                            "Deepest floor",
+                            runs.iter()
+                                .map(|run| run.floors_cleared)
+                                .max()
+                                .unwrap_or_default()
+                                .to_string(),
+                        );
+                        stat_row(
+                            parent,
+                            "Enemies defeated",
+                            runs.iter()
+                                .map(|run| run.enemies_defeated)
+                                .sum::<u32>()
+                                .to_string(),
+                        );
+                        stat_row(
+                            parent,
+                            "Time played",
+                            time_label(runs.iter().map(|run| run.time_played).sum()),
+                        );
+                        stat_row(parent, "Total honey", history.total_honey().to_string());
+                        stat_row(parent, "Starting gold", history.starting_gold().to_string());
+                    });
+
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(2.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(if history.runs.is_empty() {
+                                "No runs yet"
+                            } else {
+                                "Recent runs"
+                            }),
+                            TextFont::from_font_size(14.0),
+                            TextColor(Color::srgb(1.0, 0.85, 0.5)),
+                        ));
+                        for run in history.runs.iter().rev().take(PROFILE_RUNS) {
+                            parent.spawn((
+                                Text::new(run.short_label()),
+                                TextFont::from_font_size(12.0),
+                                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                            ));
+                        }
+                    });
+
+                parent
+                    .spawn((box_node(Vec2::new(140.0, 36.0), BUTTON_COLOR), Focusable))
+                    .with_child(label_node("Back", 16.0, Color::WHITE))
+                    .observe(recolor_node_on::<Pointer<Over>>(BUTTON_HOVER_COLOR))
+                    .observe(recolor_node_on::<Pointer<Out>>(BUTTON_COLOR))
+                    .observe(back_to_main_menu_on::<Pointer<Click>>());
+            });
+    }
+
+    pub fn despawn_profile_screen(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<ProfileScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    fn back_to_main_menu_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::MainMenu);
+        }
+    }
+}
@@ -0,0 +1,414 @@
+pub mod settings {
+    use std::fmt::Debug;
+
+    use bevy::audio::GlobalVolume;
+    use bevy::prelude::*;
+    use bevy::window::{MonitorSelection, PrimaryWindow, WindowMode, WindowResolution};
+    use serde::{Deserialize, Serialize};
+
+    use crate::focus::focus::Focusable;
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::{recolor_node_on, GameConfig, GameState};
+
+    const SETTINGS_PATH: &str = "settings.json";
+    const RESOLUTIONS: [(u32, u32); 4] = [(640, 480), (800, 600), (1024, 768), (1280, 960)];
+    const UI_SCALE_RANGE: (f32, f32) = (0.5, 2.0);
+    const GAME_SPEED_RANGE: (f32, f32) = (0.25, 3.0);
+    const VOLUME_RANGE: (f32, f32) = (0.0, 1.0);
+    const ROW_GAP: f32 = 8.0;
+    const LABEL_WIDTH: f32 = 150.0;
+    const VALUE_WIDTH: f32 = 110.0;
+    const ARROW_SIZE: Vec2 = Vec2::new(28.0, 24.0);
+    const BUTTON_COLOR: Color = Color::srgb(0.25, 0.25, 0.4);
+    const BUTTON_HOVER_COLOR: Color = Color::srgb(0.35, 0.35, 0.6);
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub enum DisplayMode {
+        #[default]
+        Windowed,
+        Borderless,
+        Fullscreen,
+    }
+
+    impl DisplayMode {
+        const ALL: [DisplayMode; 3] = [
+            DisplayMode::Windowed,
+            DisplayMode::Borderless,
+            DisplayMode::Fullscreen,
+        ];
+
+        fn window_mode(self) -> WindowMode {
+            match self {
+                DisplayMode::Windowed => WindowMode::Windowed,
+                DisplayMode::Borderless => {
+                    WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+                }
+                DisplayMode::Fullscreen => WindowMode::Fullscreen(MonitorSelection::Current),
+            }
+        }
+    }
+
+    /// Player options, read from `settings.json` at startup and written back whenever they
+    /// change on the settings screen.
+    #[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct Settings {
+        pub resolution: (u32, u32),
+        pub display_mode: DisplayMode,
+        /// Sizes the menus and the battle HUD alike.
+        pub ui_scale: f32,
+        /// Multiplies how fast game time passes, and with it every battle timer.
+        pub game_speed: f32,
+        pub master_volume: f32,
+        pub music_volume: f32,
+        pub sfx_volume: f32,
+        pub show_inspector: bool,
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Settings {
+                resolution: RESOLUTIONS[0],
+                display_mode: DisplayMode::Windowed,
+                ui_scale: 1.0,
+                game_speed: 1.0,
+                master_volume: 1.0,
+                music_volume: 0.8,
+                sfx_volume: 0.8,
+                show_inspector: true,
+            }
+        }
+    }
+
+    impl Settings {
+        /// A missing or unreadable file just means starting with the defaults.
+        pub fn load() -> Settings {
+            let Ok(contents) = std::fs::read_to_string(SETTINGS_PATH) else {
+                return Settings::default();
+            };
+            let settings: Settings = serde_json::from_str(&contents).unwrap_or_else(|err| {
+                println!("[settings] Ignoring broken {}: {}", SETTINGS_PATH, err);
+                Settings::default()
+            });
+            settings.clamped()
+        }
+
+        /// Pulls hand-edited values back into the ranges the settings screen allows.
+        fn clamped(self) -> Settings {
+            let defaults = Settings::default();
+            let clamp = |value: f32, (min, max): (f32, f32), default: f32| {
+                if value.is_finite() {
+                    value.clamp(min, max)
+                } else {
+                    default
+                }
+            };
+            Settings {
+                resolution: if RESOLUTIONS.contains(&self.resolution) {
+                    self.resolution
+                } else {
+                    defaults.resolution
+                },
+                ui_scale: clamp(self.ui_scale, UI_SCALE_RANGE, defaults.ui_scale),
+                game_speed: clamp(self.game_speed, GAME_SPEED_RANGE, defaults.game_speed),
+                master_volume: clamp(self.master_volume, VOLUME_RANGE, defaults.master_volume),
+                music_volume: clamp(self.music_volume, VOLUME_RANGE, defaults.music_volume),
+                sfx_volume: clamp(self.sfx_volume, VOLUME_RANGE, defaults.sfx_volume),
+                ..self
+            }
+        }
+
+        pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+            std::fs::write(SETTINGS_PATH, serde_json::to_string_pretty(self)?)?;
+            Ok(())
+        }
+
+        pub fn window(&self) -> Window {
+            let (width, height) = self.resolution;
+            Window {
+                title: "Bee Game".to_string(),
+                resolution: WindowResolution::new(width as f32, height as f32),
+                mode: self.display_mode.window_mode(),
+                ..default()
+            }
+        }
+
+        /// The screen size to lay out with until the window reports its real size.
+        pub fn game_config(&self) -> GameConfig {
+            GameConfig {
+                screen_width: self.resolution.0 as f32,
+                screen_height: self.resolution.1 as f32,
+            }
+        }
+    }
+
+    pub fn inspector_enabled(settings: Res<Settings>) -> bool {
+        settings.show_inspector
+    }
+
+    #[derive(Clone, Copy)]
+    enum SettingKind {
+        Resolution,
+        DisplayMode,
+        UiScale,
+        GameSpeed,
+        MasterVolume,
+        MusicVolume,
+        SfxVolume,
+        Inspector,
+    }
+
+    impl SettingKind {
+        const ALL: [SettingKind; 8] = [
+            SettingKind::Resolution,
+            SettingKind::DisplayMode,
+            SettingKind::UiScale,
+            SettingKind::GameSpeed,
+            SettingKind::MasterVolume,
+            SettingKind::MusicVolume,
+            SettingKind::SfxVolume,
+            SettingKind::Inspector,
+        ];
+
+        fn label(self) -> &'static str {
+            match self {
+                SettingKind::Resolution => "Resolution",
+                SettingKind::DisplayMode => "Window mode",
+                SettingKind::UiScale => "UI scale",
+                SettingKind::GameSpeed => "Game speed",
+                SettingKind::MasterVolume => "Master volume",
+                SettingKind::MusicVolume => "Music volume",
+                SettingKind::SfxVolume => "Effects volume",
+                SettingKind::Inspector => "Inspector",
+            }
+        }
+
+        fn value(self, settings: &Settings) -> String {
+            let percent = |volume: f32| format!("{:.0}%", volume * 100.0);
+            match self {
+                SettingKind::Resolution => {
+                    format!("{}x{}", settings.resolution.0, settings.resolution.1)
+                }
+                SettingKind::DisplayMode => format!("{:?}", settings.display_mode),
+                SettingKind::UiScale => format!("{:.2}x", settings.ui_scale),
+                SettingKind::GameSpeed => format!("{:.2}x", settings.game_speed),
+                SettingKind::MasterVolume => percent(settings.master_volume),
+                SettingKind::MusicVolume => percent(settings.music_volume),
+                SettingKind::SfxVolume => percent(settings.sfx_volume),
+                SettingKind::Inspector => {
+                    if settings.show_inspector { "On" } else { "Off" }.to_string()
+                }
+            }
+        }
+
+        /// Moves the setting one step up or down; lists wrap around, numbers are clamped.
+        fn adjust(self, settings: &mut Settings, step: i32) {
+            let cycle =
+                |index: usize, len: usize| (index as i32 + step).rem_euclid(len as i32) as usize;
+            let nudge = |value: f32, by: f32, (min, max): (f32, f32)| {
+                (value + by * step as f32).clamp(min, max)
+            };
+            match self {
+                SettingKind::Resolution => {
+                    let index = RESOLUTIONS
+                        .iter()
+                        .position(|resolution| *resolution == settings.resolution)
+                        .unwrap_or(0);
+                    settings.resolution = RESOLUTIONS[cycle(index, RESOLUTIONS.len())];
+                }
+                SettingKind::DisplayMode => {
+                    let index = DisplayMode::ALL
+                        .iter()
+                        .position(|mode| *mode == settings.display_mode)
+                        .unwrap_or(0);
+                    settings.display_mode = DisplayMode::ALL[cycle(index, DisplayMode::ALL.len())];
+                }
+                SettingKind::UiScale => {
+                    settings.ui_scale = nudge(settings.ui_scale, 0.25, UI_SCALE_RANGE)
+                }
+                SettingKind::GameSpeed => {
+                    settings.game_speed = nudge(settings.game_speed, 0.25, GAME_SPEED_RANGE)
+                }
+                SettingKind::MasterVolume => {
+                    settings.master_volume = nudge(settings.master_volume, 0.1, VOLUME_RANGE)
+                }
+                SettingKind::MusicVolume => {
+                    settings.music_volume = nudge(settings.music_volume, 0.1, VOLUME_RANGE)
+                }
+                SettingKind::SfxVolume => {
+                    settings.sfx_volume = nudge(settings.sfx_volume, 0.1, VOLUME_RANGE)
+                }
+                SettingKind::Inspector => settings.show_inspector = !settings.show_inspector,
+            }
+        }
+    }
+
+    /// Pushes the settings out to the window, time and audio whenever they change,
+    /// including once at startup.
+    pub fn apply_settings(
+        mut commands: Commands,
+        settings: Res<Settings>,
+        mut ui_scale: ResMut<UiScale>,
+        mut time: ResMut<Time<Virtual>>,
+        mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    ) {
+        if !settings.is_changed() {
+            return;
+        }
+        if let Ok(mut window) = window_query.get_single_mut() {
+            let (width, height) = settings.resolution;
+            window.resolution.set(width as f32, height as f32);
+            window.mode = settings.display_mode.window_mode();
+        }
+        ui_scale.0 = settings.ui_scale;
+        time.set_relative_speed(settings.game_speed);
+        commands.insert_resource(GlobalVolume::new(settings.master_volume));
+    }
+
+    #[derive(Component)]
+    pub struct SettingsScreen;
+
+    #[derive(Component)]
+    pub struct SettingsContent;
+
+    pub fn spawn_settings_screen(mut commands: Commands) {
+        commands
+            .spawn((
+                Name::new("Settings Screen"),
+                screen_node(Color::srgb(0.05, 0.05, 0.1)),
+                SettingsScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new("Settings"),
+                    TextFont::from_font_size(28.0),
+                    TextColor(Color::WHITE),
+                ));
+                parent.spawn((
+                    Name::new("Settings Content"),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(ROW_GAP),
+                        margin: UiRect::vertical(Val::Px(10.0)),
+                        ..default()
+                    },
+                    SettingsContent,
+                ));
+                parent
+                    .spawn(Node {
+                        column_gap: Val::Px(20.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        spawn_menu_button(parent, "Controls")
+                            .observe(open_controls_on::<Pointer<Click>>());
+                        spawn_menu_button(parent, "Back")
+                            .observe(back_to_main_menu_on::<Pointer<Click>>());
+                    });
+            });
+    }
+
+    fn spawn_menu_button<'a>(parent: &'a mut ChildBuilder, label: &str) -> EntityCommands<'a> {
+        let mut button = parent.spawn((box_node(Vec2::new(120.0, 32.0), BUTTON_COLOR), Focusable));
+        button
+            .with_child(label_node(label, 16.0, Color::WHITE))
+            .observe(recolor_node_on::<Pointer<Over>>(BUTTON_HOVER_COLOR))
+            .observe(recolor_node_on::<Pointer<Out>>(BUTTON_COLOR));
+        button
+    }
+
+    pub fn despawn_settings_screen(
+        mut commands: Commands,
+        screen_query: Query<Entity, With<SettingsScreen>>,
+    ) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+    }
+
+    fn spawn_arrow(parent: &mut ChildBuilder, kind: SettingKind, step: i32) {
+        parent
+            .spawn((box_node(ARROW_SIZE, BUTTON_COLOR), Focusable))
+            .with_child(label_node(
+                if step < 0 { "<" } else { ">" },
+                14.0,
+                Color::WHITE,
+            ))
+            .observe(recolor_node_on::<Pointer<Over>>(BUTTON_HOVER_COLOR))
+            .observe(recolor_node_on::<Pointer<Out>>(BUTTON_COLOR))
+            .observe(adjust_setting_on::<Pointer<Click>>(kind, step));
+    }
+
+    /// Rebuilds the list of options whenever one of them changes.
+    pub fn refresh_settings_screen(
+        mut commands: Commands,
+        settings: Res<Settings>,
+        new_screen: Query<(), Added<SettingsContent>>,
+        content_query: Query<Entity, With<SettingsContent>>,
+    ) {
+        if !settings.is_changed() && new_screen.is_empty() {
+            return;
+        }
+        let Ok(content) = content_query.get_single() else {
+            return;
+        };
+
+        commands.entity(content).despawn_descendants();
+        commands.entity(content).with_children(|parent| {
+            for kind in SettingKind::ALL {
+                parent
+                    .spawn(Node {
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(8.0),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Text::new(kind.label()),
+                            TextFont::from_font_size(15.0),
+                            TextColor(Color::WHITE),
+                            Node {
+                                width: Val::Px(LABEL_WIDTH),
+                                ..default()
+                            },
+                        ));
+                        spawn_arrow(parent, kind, -1);
+                        parent.spawn((
+                            label_node(kind.value(&settings), 15.0, Color::srgb(1.0, 0.85, 0.5)),
+                            Node {
+                                width: Val::Px(VALUE_WIDTH),
+                                ..default()
+                            },
+                        ));
+                        spawn_arrow(parent, kind, 1);
+                    });
+            }
+        });
+    }
+
+    fn adjust_setting_on<E: Debug + Clone + Reflect>(
+        kind: SettingKind,
+        step: i32,
+    ) -> impl Fn(Trigger<E>, ResMut<Settings>) {
+        move |_ev, mut settings| {
+            kind.adjust(&mut settings, step);
+            if let Err(err) = settings.save() {
+                println!("[settings] Could not save {}: {}", SETTINGS_PATH, err);
+            }
+        }
+    }
+
+    fn open_controls_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::Controls);
+        }
+    }
+
+    fn back_to_main_menu_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::MainMenu);
+        }
+    }
+}
@@ -0,0 +1,452 @@
+pub mod shop {
+    use std::fmt::Debug;
+
+    use bevy::prelude::*;
+    use rand::random_range;
+
+    use crate::card::card::Deck;
+    use crate::focus::focus::Focusable;
+    use crate::layout::layout::{box_node, label_node, screen_node};
+    use crate::loot::loot::Reward;
+    use crate::relics::relics::{Relic, Relics};
+    use crate::skills::skills::Class;
+    use crate::{recolor_node_on, GameState, Inventory, LootItem, LootRarity, PlayerEntity};
+
+    const CARD_OFFERS: usize = 3;
+    const ITEM_OFFERS: usize = 2;
+    const CARD_REMOVAL_PRICE: u32 = 75;
+    const ROW_SIZE: Vec2 = Vec2::new(280.0, 28.0);
+    const ROW_GAP: f32 = 4.0;
+    const COLUMN_GAP: f32 = 40.0;
+    /// Rows per page of the right-hand column; longer lists get page buttons below them.
+    const MAX_LISTED: usize = 9;
+    const PAGE_BUTTON_SIZE: Vec2 = Vec2::new(40.0, 28.0);
+    const DISABLED_COLOR: Color = Color::srgba(0.3, 0.3, 0.3, 0.7);
+
+    struct ShopOffer {
+        reward: Reward,
+        price: u32,
+        sold: bool,
+    }
+
+    /// What this shop visit has for sale. Rolled when the shop opens.
+    #[derive(Resource, Default)]
+    pub struct ShopStock {
+        offers: Vec<ShopOffer>,
+        removal_used: bool,
+        /// Whether the right-hand column lists the deck for card removal instead of items to sell.
+        removing_card: bool,
+        /// Page of the right-hand column being shown.
+        list_page: usize,
+    }
+
+    #[derive(Component)]
+    pub struct ShopScreen;
+
+    #[derive(Component)]
+    pub struct ShopContent;
+
+    fn offer_label(reward: &Reward) -> String {
+        match reward {
+            Reward::Card(effect) => effect.describe(),
+            _ => reward.label(),
+        }
+    }
+
+    fn sell_price(item: &LootItem) -> u32 {
+        item.rarity.price() / 2
+    }
+
+    pub fn open_shop(mut commands: Commands, class_query: Query<&Class, With<PlayerEntity>>) {
+        let class = class_query.get_single().unwrap_or(&Class::Warrior);
+
+        let mut offers = vec![];
+        let mut pool = class.card_pool();
+        for _ in 0..CARD_OFFERS.min(pool.len()) {
+            let (effect, rarity) = pool.swap_remove(random_range(0..pool.len()));
+            offers.push(ShopOffer {
+                reward: Reward::Card(effect),
+                price: rarity.price(),
+                sold: false,
+            });
+        }
+        for _ in 0..ITEM_OFFERS {
+            let rarity = match random_range(0..10) {
+                0..=5 => LootRarity::Common,
+                6..=8 => LootRarity::Rare,
+                _ => LootRarity::Epic,
+            };
+            let name = match random_range(0..2) {
+                0 => "Health Potion",
+                _ => "Magic Sword",
+            };
+            offers.push(ShopOffer {
+                reward: Reward::Item(LootItem {
+                    name: name.to_string(),
+                    rarity,
+                }),
+                price: rarity.price(),
+                sold: false,
+            });
+        }
+        offers.push(ShopOffer {
+            reward: Reward::Relic(Relic::get_random_relic()),
+            price: LootRarity::Epic.price(),
+            sold: false,
+        });
+
+        commands.insert_resource(ShopStock {
+            offers,
+            ..default()
+        });
+
+        commands
+            .spawn((
+                Name::new("Shop Screen"),
+                screen_node(Color::srgb(0.15, 0.12, 0.05)),
+                ShopScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Name::new("Shop Content"),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.0),
+                        ..default()
+                    },
+                    ShopContent,
+                ));
+
+                parent
+                    .spawn((
+                        Name::new("Leave Shop Button"),
+                        box_node(Vec2::new(120.0, 36.0), Color::srgb(0.4, 0.4, 0.4)),
+                    ))
+                    .with_child(label_node("Leave", 16.0, Color::WHITE))
+                    .observe(recolor_node_on::<Pointer<Over>>(Color::srgb(0.6, 0.6, 0.6)))
+                    .observe(recolor_node_on::<Pointer<Out>>(Color::srgb(0.4, 0.4, 0.4)))
+                    .insert(Focusable)
+                    .observe(leave_shop_on::<Pointer<Click>>());
+            });
+    }
+
+    pub fn close_shop(mut commands: Commands, screen_query: Query<Entity, With<ShopScreen>>) {
+        for screen in screen_query.iter() {
+            commands.entity(screen).despawn_recursive();
+        }
+        commands.remove_resource::<ShopStock>();
+    }
+
+    fn spawn_row<'a>(
+        parent: &'a mut ChildBuilder,
+        label: String,
+        color: Color,
+        text_color: Color,
+    ) -> EntityCommands<'a> {
+        let mut row = parent.spawn(box_node(ROW_SIZE, color));
+        row.with_child(label_node(label, 13.0, text_color));
+        row
+    }
+
+    fn column_node() -> Node {
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(ROW_GAP),
+            width: Val::Px(ROW_SIZE.x),
+            ..default()
+        }
+    }
+
+    fn spawn_heading(parent: &mut ChildBuilder, text: &str) {
+        parent.spawn((
+            Text::new(text),
+            TextFont::from_font_size(14.0),
+            TextColor(Color::WHITE),
+        ));
+    }
+
+    /// Rebuilds the lists whenever gold, items, the deck or the stock change.
+    pub fn refresh_shop_screen(
+        mut commands: Commands,
+        inventory: Res<Inventory>,
+        deck: Res<Deck>,
+        stock: Option<Res<ShopStock>>,
+        new_screen: Query<(), Added<ShopContent>>,
+        content_query: Query<Entity, With<ShopContent>>,
+    ) {
+        let Some(stock) = stock else {
+            return;
+        };
+        if !inventory.is_changed()
+            && !deck.is_changed()
+            && !stock.is_changed()
+            && new_screen.is_empty()
+        {
+            return;
+        }
+        let Ok(content) = content_query.get_single() else {
+            return;
+        };
+
+        commands.entity(content).despawn_descendants();
+        commands.entity(content).with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("Shop        Gold: {}", inventory.gold)),
+                TextColor(Color::srgb(1.0, 0.85, 0.2)),
+            ));
+            parent
+                .spawn(Node {
+                    align_items: AlignItems::FlexStart,
+                    column_gap: Val::Px(COLUMN_GAP),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(column_node()).with_children(|parent| {
+                        spawn_offers(parent, &stock, &inventory, &deck);
+                    });
+                    parent.spawn(column_node()).with_children(|parent| {
+                        if stock.removing_card {
+                            spawn_heading(parent, "Your deck");
+                            let first = first_listed(stock.list_page, deck.cards.len());
+                            for (i, effect) in
+                                deck.cards.iter().enumerate().skip(first).take(MAX_LISTED)
+                            {
+                                spawn_row(
+                                    parent,
+                                    effect.describe(),
+                                    Color::srgb(0.5, 0.2, 0.2),
+                                    Color::WHITE,
+                                )
+                                .insert(Focusable)
+                                .observe(remove_card_on::<
+                                    Pointer<Click>,
+                                >(
+                                    i
+                                ));
+                            }
+                            spawn_page_buttons(parent, first, deck.cards.len());
+                        } else {
+                            spawn_heading(parent, "Sell");
+                            let first = first_listed(stock.list_page, inventory.items.len());
+                            for (i, item) in inventory
+                                .items
+                                .iter()
+                                .enumerate()
+                                .skip(first)
+                                .take(MAX_LISTED)
+                            {
+                                spawn_row(
+                                    parent,
+                                    format!("{} +{}g", item.name, sell_price(item)),
+                                    item.rarity.get_color(),
+                                    item.rarity.get_text_color(),
+                                )
+                                .insert(Focusable)
+                                .observe(sell_item_on::<
+                                    Pointer<Click>,
+                                >(
+                                    i
+                                ));
+                            }
+                            spawn_page_buttons(parent, first, inventory.items.len());
+                        }
+                    });
+                });
+        });
+    }
+
+    /// The left-hand column: the stock for sale, then the card removal service.
+    fn spawn_offers(
+        parent: &mut ChildBuilder,
+        stock: &ShopStock,
+        inventory: &Inventory,
+        deck: &Deck,
+    ) {
+        spawn_heading(parent, "For sale");
+        for (i, offer) in stock.offers.iter().enumerate() {
+            let affordable = inventory.gold >= offer.price;
+            let label = if offer.sold {
+                format!("{} - sold", offer_label(&offer.reward))
+            } else {
+                format!("{} - {}g", offer_label(&offer.reward), offer.price)
+            };
+            let rarity = offer.reward.rarity();
+            let (color, text_color) = if offer.sold || !affordable {
+                (DISABLED_COLOR, Color::srgb(0.6, 0.6, 0.6))
+            } else {
+                (rarity.get_color(), rarity.get_text_color())
+            };
+            let mut row = spawn_row(parent, label, color, text_color);
+            if !offer.sold {
+                row.insert(Focusable)
+                    .observe(buy_offer_on::<Pointer<Click>>(i));
+            }
+        }
+
+        let can_remove =
+            !stock.removal_used && inventory.gold >= CARD_REMOVAL_PRICE && deck.cards.len() > 1;
+        let removal_label = if stock.removal_used {
+            "Card removed".to_string()
+        } else if stock.removing_card {
+            "Pick a card to remove ->".to_string()
+        } else {
+            format!("Remove a card - {}g", CARD_REMOVAL_PRICE)
+        };
+        parent.spawn(Node {
+            height: Val::Px(ROW_SIZE.y),
+            ..default()
+        });
+        let mut removal_row = spawn_row(
+            parent,
+            removal_label,
+            if can_remove {
+                Color::srgb(0.5, 0.2, 0.2)
+            } else {
+                DISABLED_COLOR
+            },
+            Color::WHITE,
+        );
+        if can_remove {
+            removal_row
+                .insert(Focusable)
+                .observe(toggle_card_removal_on::<Pointer<Click>>());
+        }
+    }
+
+    /// Index of the first entry on `page`, pulled back to the last page if the list has
+    /// shrunk since the page was picked.
+    fn first_listed(page: usize, len: usize) -> usize {
+        let last_page = len.saturating_sub(1) / MAX_LISTED;
+        page.min(last_page) * MAX_LISTED
+    }
+
+    fn spawn_page_buttons(parent: &mut ChildBuilder, first: usize, len: usize) {
+        if len <= MAX_LISTED {
+            return;
+        }
+        let page = first / MAX_LISTED;
+        let pages = len.div_ceil(MAX_LISTED);
+        parent
+            .spawn(Node {
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(16.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                let targets = [
+                    ("<", page.checked_sub(1)),
+                    (">", Some(page + 1).filter(|next| *next < pages)),
+                ];
+                for (i, (label, target)) in targets.into_iter().enumerate() {
+                    if i == 1 {
+                        parent.spawn((
+                            Text::new(format!("Page {}/{}", page + 1, pages)),
+                            TextFont::from_font_size(13.0),
+                            TextColor(Color::WHITE),
+                        ));
+                    }
+                    let color = if target.is_some() {
+                        Color::srgb(0.4, 0.4, 0.4)
+                    } else {
+                        DISABLED_COLOR
+                    };
+                    let mut button = parent.spawn(box_node(PAGE_BUTTON_SIZE, color));
+                    button.with_child(label_node(label, 16.0, Color::WHITE));
+                    if let Some(target) = target {
+                        button
+                            .insert(Focusable)
+                            .observe(show_page_on::<Pointer<Click>>(target));
+                    }
+                }
+            });
+    }
+
+    fn show_page_on<E: Debug + Clone + Reflect>(
+        page: usize,
+    ) -> impl Fn(Trigger<E>, ResMut<ShopStock>) {
+        move |_ev, mut stock| {
+            stock.list_page = page;
+        }
+    }
+
+    fn buy_offer_on<E: Debug + Clone + Reflect>(
+        index: usize,
+    ) -> impl Fn(
+        Trigger<E>,
+        (
+            ResMut<ShopStock>,
+            ResMut<Inventory>,
+            ResMut<Deck>,
+            ResMut<Relics>,
+        ),
+    ) {
+        move |_ev, (mut stock, mut inventory, mut deck, mut relics)| {
+            let Some(offer) = stock.offers.get_mut(index) else {
+                return;
+            };
+            if offer.sold || inventory.gold < offer.price {
+                return;
+            }
+            if offer.reward.needs_inventory_slot() && !inventory.has_room() {
+                println!("[buy_offer_on] No room for {}", offer.reward.label());
+                return;
+            }
+            inventory.gold -= offer.price;
+            offer.sold = true;
+            offer
+                .reward
+                .clone()
+                .claim(&mut inventory, &mut deck, &mut relics);
+        }
+    }
+
+    fn sell_item_on<E: Debug + Clone + Reflect>(
+        index: usize,
+    ) -> impl Fn(Trigger<E>, ResMut<Inventory>) {
+        move |_ev, mut inventory| {
+            if index >= inventory.items.len() {
+                return;
+            }
+            let item = inventory.items.remove(index);
+            inventory.gold += sell_price(&item);
+            println!("[sell_item_on] Sold {}", item.name);
+        }
+    }
+
+    fn toggle_card_removal_on<E: Debug + Clone + Reflect>() -> impl Fn(Trigger<E>, ResMut<ShopStock>)
+    {
+        move |_ev, mut stock| {
+            stock.removing_card = !stock.removing_card;
+            stock.list_page = 0;
+        }
+    }
+
+    fn remove_card_on<E: Debug + Clone + Reflect>(
+        index: usize,
+    ) -> impl Fn(Trigger<E>, (ResMut<ShopStock>, ResMut<Inventory>, ResMut<Deck>)) {
+        move |_ev, (mut stock, mut inventory, mut deck)| {
+            if stock.removal_used
+                || inventory.gold < CARD_REMOVAL_PRICE
+                || deck.cards.len() <= 1
+                || index >= deck.cards.len()
+            {
+                return;
+            }
+            let removed = deck.cards.remove(index);
+            inventory.gold -= CARD_REMOVAL_PRICE;
+            stock.removal_used = true;
+            stock.removing_card = false;
+            stock.list_page = 0;
+            println!("[remove_card_on] Removed {}", removed.describe());
+        }
+    }
+
+    fn leave_shop_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, ResMut<NextState<GameState>>) {
+        move |_ev, mut next_state| {
+            next_state.set(GameState::Battle);
+        }
+    }
+}
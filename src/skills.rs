@@ -1,5 +1,12 @@
 pub mod skills {
     use bevy::prelude::*;
+    use serde::Deserialize;
+
+    use crate::card::card::CardEffect;
+    use crate::LootRarity;
+
+    const BASE_HEALTH: i32 = 50;
+    const HEALTH_PER_STAMINA: i32 = 5;
 
     #[derive(Component, Clone, Default)]
     pub struct Stats {
@@ -10,7 +17,63 @@ pub mod skills {
         pub intelligence: usize,
     }
 
-    #[derive(Component, Clone)]
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+    pub enum StatKind {
+        Strength,
+        Agility,
+        Stamina,
+        Perception,
+        Intelligence,
+    }
+
+    impl StatKind {
+        pub const ALL: [StatKind; 5] = [
+            StatKind::Strength,
+            StatKind::Agility,
+            StatKind::Stamina,
+            StatKind::Perception,
+            StatKind::Intelligence,
+        ];
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                StatKind::Strength => "Strength",
+                StatKind::Agility => "Agility",
+                StatKind::Stamina => "Stamina",
+                StatKind::Perception => "Perception",
+                StatKind::Intelligence => "Intelligence",
+            }
+        }
+    }
+
+    impl Stats {
+        pub fn get(&self, stat: StatKind) -> usize {
+            match stat {
+                StatKind::Strength => self.strength,
+                StatKind::Agility => self.agility,
+                StatKind::Stamina => self.stamina,
+                StatKind::Perception => self.perception,
+                StatKind::Intelligence => self.intelligence,
+            }
+        }
+
+        /// The player's max health, which grows with stamina.
+        pub fn max_health(&self) -> i32 {
+            BASE_HEALTH + self.stamina as i32 * HEALTH_PER_STAMINA
+        }
+
+        pub fn get_mut(&mut self, stat: StatKind) -> &mut usize {
+            match stat {
+                StatKind::Strength => &mut self.strength,
+                StatKind::Agility => &mut self.agility,
+                StatKind::Stamina => &mut self.stamina,
+                StatKind::Perception => &mut self.perception,
+                StatKind::Intelligence => &mut self.intelligence,
+            }
+        }
+    }
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
     pub enum Class {
         Warrior,
         Rogue,
@@ -18,4 +81,123 @@ pub mod skills {
         Healer,
         Assassin,
     }
+
+    impl Class {
+        pub const ALL: [Class; 5] = [
+            Class::Warrior,
+            Class::Rogue,
+            Class::Mage,
+            Class::Healer,
+            Class::Assassin,
+        ];
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                Class::Warrior => "Warrior",
+                Class::Rogue => "Rogue",
+                Class::Mage => "Mage",
+                Class::Healer => "Healer",
+                Class::Assassin => "Assassin",
+            }
+        }
+
+        pub fn description(&self) -> &'static str {
+            match self {
+                Class::Warrior => "Heavy hits, stuns and shields",
+                Class::Rogue => "Poisons that wear enemies down",
+                Class::Mage => "Burning spells and big single blows",
+                Class::Healer => "Outlasts enemies with heals",
+                Class::Assassin => "Fast strikes that finish fights quickly",
+            }
+        }
+
+        /// A new run with this class starts with every common card from its pool.
+        pub fn starting_deck(&self) -> Vec<CardEffect> {
+            self.card_pool()
+                .into_iter()
+                .filter(|(_, rarity)| *rarity == LootRarity::Common)
+                .map(|(effect, _)| effect)
+                .collect()
+        }
+
+        /// Cards this class can be offered after a fight, with how rare each one is.
+        pub fn card_pool(&self) -> Vec<(CardEffect, LootRarity)> {
+            match self {
+                Class::Warrior => vec![
+                    (CardEffect::DirectDamage(10), LootRarity::Common),
+                    (CardEffect::DirectDamage(14), LootRarity::Common),
+                    (CardEffect::Stun { duration: 1.0 }, LootRarity::Common),
+                    (CardEffect::Shield(10), LootRarity::Common),
+                    (CardEffect::Heal(8), LootRarity::Rare),
+                    (CardEffect::DirectDamage(22), LootRarity::Rare),
+                    (CardEffect::Stun { duration: 2.5 }, LootRarity::Epic),
+                    (CardEffect::Shield(25), LootRarity::Epic),
+                ],
+                Class::Rogue => vec![
+                    (CardEffect::DirectDamage(8), LootRarity::Common),
+                    (
+                        CardEffect::DamageOverTime {
+                            damage: 3,
+                            duration: 3.0,
+                            frequency: 0.5,
+                        },
+                        LootRarity::Common,
+                    ),
+                    (CardEffect::Stun { duration: 1.0 }, LootRarity::Rare),
+                    (
+                        CardEffect::DamageOverTime {
+                            damage: 5,
+                            duration: 4.0,
+                            frequency: 0.4,
+                        },
+                        LootRarity::Rare,
+                    ),
+                    (CardEffect::DirectDamage(25), LootRarity::Epic),
+                ],
+                Class::Mage => vec![
+                    (CardEffect::DirectDamage(12), LootRarity::Common),
+                    (
+                        CardEffect::DamageOverTime {
+                            damage: 4,
+                            duration: 2.0,
+                            frequency: 0.5,
+                        },
+                        LootRarity::Common,
+                    ),
+                    (CardEffect::Stun { duration: 1.5 }, LootRarity::Rare),
+                    (CardEffect::DirectDamage(20), LootRarity::Rare),
+                    (
+                        CardEffect::DamageOverTime {
+                            damage: 8,
+                            duration: 4.0,
+                            frequency: 0.5,
+                        },
+                        LootRarity::Epic,
+                    ),
+                ],
+                Class::Healer => vec![
+                    (CardEffect::Heal(8), LootRarity::Common),
+                    (CardEffect::DirectDamage(8), LootRarity::Common),
+                    (CardEffect::Heal(14), LootRarity::Rare),
+                    (CardEffect::Shield(15), LootRarity::Rare),
+                    (CardEffect::Stun { duration: 1.0 }, LootRarity::Rare),
+                    (CardEffect::Heal(25), LootRarity::Epic),
+                ],
+                Class::Assassin => vec![
+                    (CardEffect::DirectDamage(12), LootRarity::Common),
+                    (
+                        CardEffect::DamageOverTime {
+                            damage: 4,
+                            duration: 2.0,
+                            frequency: 0.3,
+                        },
+                        LootRarity::Common,
+                    ),
+                    (CardEffect::DirectDamage(18), LootRarity::Rare),
+                    (CardEffect::Stun { duration: 2.0 }, LootRarity::Rare),
+                    (CardEffect::DirectDamage(30), LootRarity::Epic),
+                ],
+            }
+        }
+    }
 }
@@ -0,0 +1,208 @@
+pub mod status_icons {
+    use std::fmt::Debug;
+
+    use bevy::{prelude::*, sprite::Anchor, text::TextBounds};
+
+    use crate::card::card::Effects;
+
+    const ICON_SIZE: f32 = 18.0;
+    const ICON_SPACING: f32 = 22.0;
+    const TOOLTIP_WIDTH: f32 = 150.0;
+    const TOOLTIP_Z_LAYER: f32 = 0.5;
+
+    /// Row of icons next to a combatant, one per kind of lingering effect.
+    #[derive(Component)]
+    pub struct StatusIconRow;
+
+    /// Effects of the same kind share an icon; `stacks` counts how many there are.
+    #[derive(Component)]
+    pub struct StatusIcon {
+        path: &'static str,
+        stacks: usize,
+        tooltip: String,
+    }
+
+    #[derive(Component)]
+    pub struct StatusIconText;
+
+    #[derive(Component)]
+    pub struct StatusTooltip;
+
+    struct StatusGroup {
+        path: &'static str,
+        stacks: usize,
+        remaining: f32,
+        tooltip: String,
+    }
+
+    fn group_effects(effects: &Effects) -> Vec<StatusGroup> {
+        let mut groups: Vec<StatusGroup> = vec![];
+        for effect in effects.effects.iter() {
+            let Some(remaining) = effect.remaining_secs() else {
+                continue;
+            };
+            let path = effect.icon_path();
+            match groups.iter_mut().find(|group| group.path == path) {
+                Some(group) => {
+                    group.stacks += 1;
+                    group.remaining = group.remaining.max(remaining);
+                    group.tooltip.push_str(&format!("\n{}", effect.describe()));
+                }
+                None => groups.push(StatusGroup {
+                    path,
+                    stacks: 1,
+                    remaining,
+                    tooltip: format!("{}\n{}", effect.name(), effect.describe()),
+                }),
+            }
+        }
+        groups
+    }
+
+    pub fn add_status_icon_row(parent: &mut ChildBuilder, position: Vec2) {
+        parent.spawn((
+            Name::new("Status Icons"),
+            Transform::from_xyz(position.x, position.y, 0.3),
+            Visibility::default(),
+            StatusIconRow,
+        ));
+    }
+
+    /// Rebuilds a row when the kinds or stack counts of effects change and otherwise just
+    /// counts down the remaining durations.
+    pub fn update_status_icons(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        owner_query: Query<(&Effects, &Children)>,
+        row_query: Query<Option<&Children>, With<StatusIconRow>>,
+        icon_query: Query<(&StatusIcon, &Children)>,
+        mut text_query: Query<&mut Text2d, With<StatusIconText>>,
+    ) {
+        for (effects, children) in owner_query.iter() {
+            let Some((row, icons)) = children
+                .iter()
+                .find_map(|child| row_query.get(*child).ok().map(|icons| (*child, icons)))
+            else {
+                continue;
+            };
+
+            let groups = group_effects(effects);
+            let current: Vec<(&'static str, usize)> = icons
+                .map(|icons| {
+                    icons
+                        .iter()
+                        .filter_map(|icon| icon_query.get(*icon).ok())
+                        .map(|(icon, _)| (icon.path, icon.stacks))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            if current.len() == groups.len()
+                && current
+                    .iter()
+                    .zip(&groups)
+                    .all(|((path, stacks), group)| *path == group.path && *stacks == group.stacks)
+            {
+                for (icon, group) in icons.into_iter().flatten().zip(&groups) {
+                    let Ok((_, icon_children)) = icon_query.get(*icon) else {
+                        continue;
+                    };
+                    for child in icon_children.iter() {
+                        if let Ok(mut text) = text_query.get_mut(*child) {
+                            text.0 = format!("{:.1}", group.remaining);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            commands.entity(row).despawn_descendants();
+            commands.entity(row).with_children(|parent| {
+                for (i, group) in groups.into_iter().enumerate() {
+                    parent
+                        .spawn((
+                            Sprite {
+                                image: asset_server.load(group.path),
+                                custom_size: Some(Vec2::splat(ICON_SIZE)),
+                                ..default()
+                            },
+                            Transform::from_xyz(i as f32 * ICON_SPACING, 0.0, 0.0),
+                            StatusIcon {
+                                path: group.path,
+                                stacks: group.stacks,
+                                tooltip: group.tooltip,
+                            },
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text2d::new(format!("{:.1}", group.remaining)),
+                                TextFont::from_font_size(10.0),
+                                TextColor(Color::WHITE),
+                                Transform::from_xyz(0.0, -ICON_SIZE / 2.0 - 6.0, 0.1),
+                                StatusIconText,
+                            ));
+                            if group.stacks > 1 {
+                                parent.spawn((
+                                    Text2d::new(format!("x{}", group.stacks)),
+                                    TextFont::from_font_size(10.0),
+                                    TextColor(Color::srgb(1.0, 1.0, 0.3)),
+                                    Transform::from_xyz(ICON_SIZE / 2.0, ICON_SIZE / 2.0, 0.1),
+                                ));
+                            }
+                        })
+                        .observe(show_status_tooltip_on::<Pointer<Over>>())
+                        .observe(hide_status_tooltip_on::<Pointer<Out>>());
+                }
+            });
+        }
+    }
+
+    fn show_status_tooltip_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, Query<&StatusIcon>, Commands) {
+        move |ev, icon_query, mut commands| {
+            let Ok(icon) = icon_query.get(ev.entity()) else {
+                return;
+            };
+            commands.entity(ev.entity()).with_children(|parent| {
+                parent
+                    .spawn((
+                        Sprite {
+                            color: Color::srgba(0.0, 0.0, 0.0, 0.85),
+                            custom_size: Some(Vec2::new(
+                                TOOLTIP_WIDTH + 8.0,
+                                16.0 * (icon.stacks + 1) as f32 + 8.0,
+                            )),
+                            anchor: Anchor::BottomLeft,
+                            ..default()
+                        },
+                        Transform::from_xyz(0.0, ICON_SIZE / 2.0, TOOLTIP_Z_LAYER),
+                        // Don't let the tooltip steal the hover from the icon underneath
+                        PickingBehavior::IGNORE,
+                        StatusTooltip,
+                    ))
+                    .with_child((
+                        Text2d::new(icon.tooltip.clone()),
+                        TextFont::from_font_size(11.0),
+                        TextColor(Color::WHITE),
+                        TextBounds::new_horizontal(TOOLTIP_WIDTH),
+                        Anchor::BottomLeft,
+                        Transform::from_xyz(4.0, 4.0, 0.1),
+                    ));
+            });
+        }
+    }
+
+    fn hide_status_tooltip_on<E: Debug + Clone + Reflect>(
+    ) -> impl Fn(Trigger<E>, Query<&Children>, Query<(), With<StatusTooltip>>, Commands) {
+        move |ev, children_query, tooltip_query, mut commands| {
+            let Ok(children) = children_query.get(ev.entity()) else {
+                return;
+            };
+            for child in children.iter() {
+                if tooltip_query.contains(*child) {
+                    commands.entity(*child).despawn_recursive();
+                }
+            }
+        }
+    }
+}